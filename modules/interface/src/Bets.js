@@ -9,8 +9,10 @@ function Main(props) {
   const { accountPair } = props
 
   const [status, setStatus] = useState('')
+  const [gameId, setGameId] = useState('0')
   const [bets, setBets] = useState([])
   const [formValue, setFormValue] = useState('0x010203040506')
+  const [stakeValue, setStakeValue] = useState('1000000000')
 
   useEffect(() => {
     let unsubscribe
@@ -25,18 +27,27 @@ function Main(props) {
       .catch(console.error)
 
     return () => unsubscribe && unsubscribe()
-  }, [api.derive.chain.bestNumber])
+  }, [api.derive.chain.bestNumber, gameId])
 
   const getBets = async () => {
-    const sessionId = await api.query.weHub.sessionId()
-    const bets = await api.query.weHub.bets(sessionId.toHuman())
-    setBets(bets.toHuman())
+    const sessionId = await api.query.weHub.sessionId(gameId)
+    const bets = await api.query.weHub.bets.entries([gameId, sessionId])
+    setBets(bets.map(([, bet]) => bet.toHuman()))
   }
 
   return (
     <Grid.Column width={8} stretched={false}>
       <h1>Bets</h1>
       <Form>
+        <Form.Field>
+          <Input
+            label="Game"
+            state="newValue"
+            type="string"
+            onChange={(_, { value }) => setGameId(value)}
+            value={gameId}
+          />
+        </Form.Field>
         <Form.Field>
           <Input
             label="Add a new bet"
@@ -46,6 +57,15 @@ function Main(props) {
             value={formValue}
           />
         </Form.Field>
+        <Form.Field>
+          <Input
+            label="Stake"
+            state="newValue"
+            type="string"
+            onChange={(_, { value }) => setStakeValue(value)}
+            value={stakeValue}
+          />
+        </Form.Field>
         <Form.Field style={{ textAlign: 'center' }}>
           <TxButton
             accountPair={accountPair}
@@ -56,8 +76,8 @@ function Main(props) {
               palletRpc: 'weHub',
               callable: 'addNewBet',
               interxType: 'EXTRINSIC',
-              inputParams: [formValue],
-              paramFields: [true],
+              inputParams: [gameId, formValue, stakeValue],
+              paramFields: [true, true, true],
             }}
           />
         </Form.Field>
@@ -68,7 +88,7 @@ function Main(props) {
       <Card fluid>
         <Card.Content>
           <Card.Description>
-            {bets.map(({ account_id, guess_numbers }, i) => (
+            {bets.map(({ account_id, guess_numbers, bet }, i) => (
               <div key={i} style={{ overflowWrap: 'break-word' }}>
                 <p>
                   <b>account_id:</b> {account_id}
@@ -76,6 +96,9 @@ function Main(props) {
                 <p>
                   <b>guess_numbers:</b> {guess_numbers}
                 </p>
+                <p>
+                  <b>bet:</b> {bet}
+                </p>
                 <hr />
               </div>
             ))}
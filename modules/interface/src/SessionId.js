@@ -12,7 +12,7 @@ function Main(props) {
     let unsubscribe
 
     api.query.weHub
-      .sessionId((sessionId) => {
+      .sessionId(0, (sessionId) => {
         setSessionId(sessionId.toHuman())
       })
       .then((unsub) => {
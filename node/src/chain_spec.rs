@@ -2,10 +2,11 @@ use sp_core::{Pair, Public, sr25519, ed25519};
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
 	SudoConfig, SystemConfig, WASM_BINARY, Signature, WeHubConfig,
+	pallet_wehub::{self, DrawMode},
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{Verify, IdentifyAccount};
+use sp_runtime::{Percent, traits::{Verify, IdentifyAccount}};
 use sc_service::ChainType;
 use hex_literal::hex;
 
@@ -217,6 +218,11 @@ fn testnet_genesis(
 		}),
 		pallet_wehub: Some(WeHubConfig {
 			offchain_authorities,
+			draw_mode: DrawMode::Offchain,
+			games: vec![pallet_wehub::classic_game(1_000_000_000)],
+			operator_fee: Percent::from_percent(10),
+			treasury_share: Percent::from_percent(0),
+			treasury_account: None,
 		}),
 	}
 }
@@ -0,0 +1,46 @@
+//! Runtime API definition for the WeHub pallet, so clients can read the lottery state without
+//! decoding its storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_wehub::{Bet, GameId, NFTRequestData, SessionIdType, SessionInfo, SessionOutcome, SessionStatus, Winners};
+
+sp_api::decl_runtime_apis! {
+	pub trait WeHubApi<AccountId, BlockNumber, Balance, GuessNumbers> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+		GuessNumbers: Codec,
+	{
+		/// The open session of the game.
+		fn session_id(game_id: GameId) -> SessionIdType;
+
+		/// The blocks left before the open session of the game closes. `None` for a wall clock schedule,
+		/// or a game that does not exist.
+		fn blocks_until_close(game_id: GameId) -> Option<BlockNumber>;
+
+		/// The free balance of the pallet account, shared by every game.
+		fn pot() -> Balance;
+
+		/// The tickets an account holds in a session, until the session is pruned.
+		fn tickets(game_id: GameId, session_id: SessionIdType, account_id: AccountId) -> Vec<Bet<AccountId, Balance, GuessNumbers>>;
+
+		/// The winners still waiting for their NFT.
+		fn pending_nft_requests() -> Vec<NFTRequestData<AccountId, Balance>>;
+
+		/// The status, numbers and payouts of a session.
+		fn session_results(game_id: GameId, session_id: SessionIdType) -> Option<SessionInfo<BlockNumber, Balance, GuessNumbers>>;
+
+		/// The tickets of a finalised session that reached a prize tier, with their hits.
+		fn session_winners(game_id: GameId, session_id: SessionIdType) -> Winners<AccountId, Balance, GuessNumbers>;
+
+		/// The reward an account can still claim from a session.
+		fn claimable_reward(game_id: GameId, session_id: SessionIdType, account_id: AccountId) -> Balance;
+
+		/// The sessions finalised in the block the API is called at.
+		fn finalised_sessions() -> Vec<SessionOutcome<AccountId, Balance, GuessNumbers>>;
+	}
+}
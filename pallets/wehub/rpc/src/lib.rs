@@ -0,0 +1,380 @@
+//! RPC interface for the WeHub pallet, under the `wehub_*` namespace.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use futures::{StreamExt, TryStreamExt};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result, futures::{Future, Sink}};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use log::warn;
+use sc_client_api::BlockchainEvents;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, Header as HeaderT, NumberFor}};
+
+pub use pallet_wehub_rpc_runtime_api::{GameId, SessionIdType, SessionOutcome, SessionStatus, WeHubApi as WeHubRuntimeApi};
+
+/// The open session of a game.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionState<BlockNumber> {
+	pub session_id: SessionIdType,
+	/// `None` while the session is not recorded yet, or was pruned.
+	pub status: Option<SessionStatus>,
+	pub start_block: Option<BlockNumber>,
+	/// `None` for a wall clock schedule.
+	pub blocks_until_close: Option<BlockNumber>,
+}
+
+/// A ticket of an account.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(
+	serialize = "Balance: std::fmt::Display, GuessNumbers: Serialize",
+	deserialize = "Balance: std::str::FromStr, GuessNumbers: Deserialize<'de>",
+))]
+pub struct Ticket<Balance, GuessNumbers> {
+	pub guess_numbers: GuessNumbers,
+	#[serde(serialize_with = "serialize_as_string", deserialize_with = "deserialize_from_string")]
+	pub stake: Balance,
+}
+
+/// A winning ticket of a session.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(
+	serialize = "AccountId: Serialize, Balance: std::fmt::Display, GuessNumbers: Serialize",
+	deserialize = "AccountId: Deserialize<'de>, Balance: std::str::FromStr, GuessNumbers: Deserialize<'de>",
+))]
+pub struct Winner<AccountId, Balance, GuessNumbers> {
+	pub account_id: AccountId,
+	pub guess_numbers: GuessNumbers,
+	#[serde(serialize_with = "serialize_as_string", deserialize_with = "deserialize_from_string")]
+	pub stake: Balance,
+	pub hits: u8,
+}
+
+/// A reward recorded for a winner.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(
+	serialize = "AccountId: Serialize, Balance: std::fmt::Display",
+	deserialize = "AccountId: Deserialize<'de>, Balance: std::str::FromStr",
+))]
+pub struct Payout<AccountId, Balance> {
+	pub account_id: AccountId,
+	#[serde(serialize_with = "serialize_as_string", deserialize_with = "deserialize_from_string")]
+	pub amount: Balance,
+}
+
+/// A session finalised in an imported block, as pushed to `wehub_subscribeSessionResults` subscribers.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(
+	serialize = "Hash: Serialize, AccountId: Serialize, Balance: std::fmt::Display, GuessNumbers: Serialize",
+	deserialize = "Hash: Deserialize<'de>, AccountId: Deserialize<'de>, Balance: std::str::FromStr, GuessNumbers: Deserialize<'de>",
+))]
+pub struct SessionResult<Hash, AccountId, Balance, GuessNumbers> {
+	/// The block that finalised the session.
+	pub block_hash: Hash,
+	pub game_id: GameId,
+	pub session_id: SessionIdType,
+	pub session_numbers: GuessNumbers,
+	pub winners: Vec<Winner<AccountId, Balance, GuessNumbers>>,
+	pub payouts: Vec<Payout<AccountId, Balance>>,
+	/// `false` when the block is imported. The result is sent again with `true` once GRANDPA finalises the block.
+	pub finalized: bool,
+}
+
+impl<Hash, AccountId, Balance, GuessNumbers> SessionResult<Hash, AccountId, Balance, GuessNumbers> {
+	fn new(block_hash: Hash, outcome: SessionOutcome<AccountId, Balance, GuessNumbers>) -> Self {
+		SessionResult {
+			block_hash,
+			game_id: outcome.game_id,
+			session_id: outcome.session_id,
+			session_numbers: outcome.session_numbers,
+			winners: outcome.winners.into_iter()
+				.map(|(bet, hits)| Winner {
+					account_id: bet.account_id,
+					guess_numbers: bet.guess_numbers,
+					stake: bet.bet,
+					hits,
+				})
+				.collect(),
+			payouts: outcome.payouts.into_iter()
+				.map(|(account_id, amount)| Payout { account_id, amount })
+				.collect(),
+			finalized: false,
+		}
+	}
+}
+
+// Balances do not fit in a javascript number, so they are sent as strings.
+fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+	serializer.serialize_str(&t.to_string())
+}
+
+fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(deserializer: D) -> std::result::Result<T, D::Error> {
+	let s = String::deserialize(deserializer)?;
+	s.parse::<T>().map_err(|_| serde::de::Error::custom("Parse from string failed"))
+}
+
+#[rpc]
+pub trait WeHubApi<BlockHash, AccountId, BlockNumber, Balance, GuessNumbers> {
+	/// RPC metadata
+	type Metadata;
+
+	/// The open session of a game and the blocks left before it closes.
+	#[rpc(name = "wehub_sessionStatus")]
+	fn session_status(&self, game_id: GameId, at: Option<BlockHash>) -> Result<SessionState<BlockNumber>>;
+
+	/// The free balance of the pallet account, shared by every game.
+	#[rpc(name = "wehub_pot")]
+	fn pot(&self, at: Option<BlockHash>) -> Result<NumberOrHex>;
+
+	/// The tickets an account holds in a session, until the session is pruned.
+	#[rpc(name = "wehub_ticketsByAccount")]
+	fn tickets_by_account(
+		&self,
+		game_id: GameId,
+		session_id: SessionIdType,
+		account_id: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Ticket<Balance, GuessNumbers>>>;
+
+	/// The winning tickets of a finalised session, until the session is pruned.
+	#[rpc(name = "wehub_winnersBySession")]
+	fn winners_by_session(
+		&self,
+		game_id: GameId,
+		session_id: SessionIdType,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Winner<AccountId, Balance, GuessNumbers>>>;
+
+	/// The reward an account can still claim from a session.
+	#[rpc(name = "wehub_claimableReward")]
+	fn claimable_reward(
+		&self,
+		game_id: GameId,
+		session_id: SessionIdType,
+		account_id: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<NumberOrHex>;
+
+	/// Pushes the result of every session finalised in an imported block, and pushes it again once GRANDPA
+	/// finalises that block.
+	#[pubsub(subscription = "wehub_sessionResults", subscribe, name = "wehub_subscribeSessionResults")]
+	fn subscribe_session_results(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<SessionResult<BlockHash, AccountId, Balance, GuessNumbers>>,
+	);
+
+	/// Stops the session results of a subscription.
+	#[pubsub(subscription = "wehub_sessionResults", unsubscribe, name = "wehub_unsubscribeSessionResults")]
+	fn unsubscribe_session_results(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+}
+
+/// Answers the `wehub_*` calls from the runtime API of the client's best or requested block.
+pub struct WeHub<C, M> {
+	client: Arc<C>,
+	subscriptions: SubscriptionManager,
+	_marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> WeHub<C, M> {
+	pub fn new(client: Arc<C>, subscriptions: SubscriptionManager) -> Self {
+		WeHub { client, subscriptions, _marker: Default::default() }
+	}
+}
+
+enum BlockNotification<Block: BlockT> {
+	Imported(Block::Hash, NumberFor<Block>),
+	Finalized(NumberFor<Block>),
+}
+
+/// Error codes of the `wehub_*` calls.
+pub enum Error {
+	/// The runtime API call failed.
+	RuntimeError,
+	/// A balance does not fit in the RPC number type.
+	DecodeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query the lottery state.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance, GuessNumbers>
+	WeHubApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Balance, GuessNumbers> for WeHub<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C::Api: WeHubRuntimeApi<Block, AccountId, BlockNumber, Balance, GuessNumbers>,
+	AccountId: Codec + Clone + Send + 'static,
+	BlockNumber: Codec,
+	Balance: Codec + Clone + Send + 'static + TryInto<NumberOrHex>,
+	GuessNumbers: Codec + Clone + Send + 'static,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn session_status(&self, game_id: GameId, at: Option<<Block as BlockT>::Hash>) -> Result<SessionState<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let session_id = api.session_id(&at, game_id).map_err(runtime_error)?;
+		let session_info = api.session_results(&at, game_id, session_id).map_err(runtime_error)?;
+		let blocks_until_close = api.blocks_until_close(&at, game_id).map_err(runtime_error)?;
+
+		Ok(SessionState {
+			session_id,
+			status: session_info.as_ref().map(|session_info| session_info.status),
+			start_block: session_info.map(|session_info| session_info.start_block),
+			blocks_until_close,
+		})
+	}
+
+	fn pot(&self, at: Option<<Block as BlockT>::Hash>) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let pot = api.pot(&at).map_err(runtime_error)?;
+		pot.try_into().map_err(|_| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to convert the pot to a number.".into(),
+			data: None,
+		})
+	}
+
+	fn tickets_by_account(
+		&self,
+		game_id: GameId,
+		session_id: SessionIdType,
+		account_id: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Ticket<Balance, GuessNumbers>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let tickets = api.tickets(&at, game_id, session_id, account_id).map_err(runtime_error)?;
+
+		Ok(tickets.into_iter()
+			.map(|bet| Ticket { guess_numbers: bet.guess_numbers, stake: bet.bet })
+			.collect())
+	}
+
+	fn winners_by_session(
+		&self,
+		game_id: GameId,
+		session_id: SessionIdType,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Winner<AccountId, Balance, GuessNumbers>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let winners = api.session_winners(&at, game_id, session_id).map_err(runtime_error)?;
+
+		Ok(winners.into_iter()
+			.map(|(bet, hits)| Winner {
+				account_id: bet.account_id,
+				guess_numbers: bet.guess_numbers,
+				stake: bet.bet,
+				hits,
+			})
+			.collect())
+	}
+
+	fn claimable_reward(
+		&self,
+		game_id: GameId,
+		session_id: SessionIdType,
+		account_id: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let reward = api.claimable_reward(&at, game_id, session_id, account_id).map_err(runtime_error)?;
+		reward.try_into().map_err(|_| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to convert the reward to a number.".into(),
+			data: None,
+		})
+	}
+
+	fn subscribe_session_results(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<SessionResult<<Block as BlockT>::Hash, AccountId, Balance, GuessNumbers>>,
+	) {
+		let client = self.client.clone();
+		// Results sent for an imported block, waiting for GRANDPA to finalise it or a block of another fork.
+		let mut unfinalized: Vec<(NumberFor<Block>, <Block as BlockT>::Hash, Vec<SessionResult<_, _, _, _>>)> = Vec::new();
+
+		let imported = self.client.import_notification_stream()
+			.map(|notification| BlockNotification::<Block>::Imported(notification.hash, *notification.header.number()));
+		let finalized = self.client.finality_notification_stream()
+			.map(|notification| BlockNotification::<Block>::Finalized(*notification.header.number()));
+
+		let results = futures::stream::select(imported, finalized)
+			.map(move |notification| match notification {
+				BlockNotification::Imported(hash, number) => {
+					let outcomes = client.runtime_api().finalised_sessions(&BlockId::hash(hash)).unwrap_or_else(|e| {
+						warn!("Unable to read the session results of block {:?}: {:?}", hash, e);
+						Vec::new()
+					});
+					let results: Vec<_> = outcomes.into_iter()
+						.map(|outcome| SessionResult::new(hash, outcome))
+						.collect();
+
+					if !results.is_empty() {
+						unfinalized.push((number, hash, results.clone()));
+					}
+					results
+				},
+				// A finality notification can cover several blocks, so every result up to its height is settled.
+				BlockNotification::Finalized(finalized_number) => {
+					let (settled, pending) = unfinalized.drain(..)
+						.partition::<Vec<_>, _>(|(number, _, _)| *number <= finalized_number);
+					unfinalized = pending;
+
+					settled.into_iter()
+						.filter(|(number, hash, _)| client.hash(*number).ok().flatten() == Some(*hash))
+						.flat_map(|(_, _, results)| results)
+						.map(|result| SessionResult { finalized: true, ..result })
+						.collect()
+				},
+			})
+			.flat_map(futures::stream::iter)
+			.map(|result| Ok::<_, ()>(Ok(result)))
+			.compat();
+
+		self.subscriptions.add(subscriber, |sink| {
+			sink
+				.sink_map_err(|e| warn!("Error sending session results: {:?}", e))
+				.send_all(results)
+				// The stream only ends when the client does, and an unsubscribe drops the sink.
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_session_results(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
@@ -0,0 +1,523 @@
+//! Benchmarks for the wehub pallet.
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, TrailingZeroInput};
+
+const SEED: u32 = 0;
+const MAX_PENDING_WINNERS: u32 = 1_000;
+const MAX_AUTHORITIES: u32 = 100;
+
+/// A 6 of 1..=100 game, so there are plenty of numbers left for losing tickets.
+fn create_game<T: Config>() -> Result<GameId, &'static str> {
+	let game = Game { max_number: 100, ..classic_game(T::MaxBet::get()) };
+
+	Ok(Pallet::<T>::do_create_game(game)?)
+}
+
+/// Unsigned calls are checked in `validate_unsigned`, so any well-formed key or signature will do.
+fn zeroed<D: Decode>() -> Result<D, &'static str> {
+	D::decode(&mut TrailingZeroInput::new(&[][..])).map_err(|_| "Cannot decode from zeroes")
+}
+
+fn guess_numbers<T: Config>(numbers: Vec<u8>) -> Result<GuessNumbersOf<T>, &'static str> {
+	numbers.try_into().map_err(|_| "Ticket exceeds MaxPickCount")
+}
+
+/// A game picking `MaxPickCount` numbers, so its prize table can have that many tiers.
+fn widest_game<T: Config>() -> GameOf<T> {
+	Game {
+		pick_count: T::MaxPickCount::get() as u8,
+		max_number: 100,
+		..classic_game(T::MaxBet::get())
+	}
+}
+
+/// `t` tiers of 1% each, from one hit up.
+fn prize_table<T: Config>(t: u32) -> Vec<PrizeTierOf<T>> {
+	(1 ..= t)
+		.map(|min_hits| PrizeTier { min_hits: min_hits as u8, percentage: Percent::from_percent(1), fixed_prize: None })
+		.collect()
+}
+
+/// The account of the authority signing the unsigned calls, followed by `a - 1` other authorities.
+fn set_authorities<T: Config>(a: u32) -> Result<(T::Public, Vec<T::AccountId>), &'static str> {
+	let signer: T::Public = zeroed()?;
+	let authorities: Vec<T::AccountId> = sp_std::iter::once(signer.clone().into_account())
+		.chain((1 .. a).map(|index| account("authority", index, SEED)))
+		.collect();
+	Authorities::<T>::put(&authorities);
+
+	Ok((signer, authorities))
+}
+
+benchmarks! {
+	// A game with `t` prize tiers.
+	create_game {
+		let t in 1 .. T::MaxPickCount::get();
+
+		let game_id = Pallet::<T>::next_game_id();
+		let game = Game { prize_table: prize_table::<T>(t), ..widest_game::<T>() };
+	}: _(RawOrigin::Root, game)
+	verify {
+		assert!(Pallet::<T>::games(game_id).is_some());
+	}
+
+	add_new_bet {
+		let game_id = create_game::<T>()?;
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let numbers = guess_numbers::<T>((1..=6).collect())?;
+	}: _(RawOrigin::Signed(caller), game_id, numbers, T::MaxBet::get())
+	verify {
+		assert_eq!(Pallet::<T>::bet_count(game_id, 0), 1);
+	}
+
+	// `b` losing and `w` jackpot tickets, finalised by the submission completing the quorum of `a` authorities
+	// that all revealed and all take a part of the fee.
+	finalize_the_session {
+		let b in 0 .. T::MaxTicketsPerSession::get();
+		let w in 0 .. T::MaxTicketsPerSession::get();
+		let a in 1 .. MAX_AUTHORITIES;
+
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+
+		let (signer, authorities) = set_authorities::<T>(a)?;
+		for (index, authority) in authorities.iter().enumerate() {
+			let secret: SecretType = [index as u8; 32];
+			Commitments::<T>::insert((game_id, session_id), authority, T::Hashing::hash_of(&secret));
+			Reveals::<T>::append(game_id, session_id, (authority.clone(), secret));
+			T::Currency::make_free_balance_be(authority, T::Currency::minimum_balance());
+		}
+		let treasury_account: T::AccountId = account("treasury", 0, SEED);
+		T::Currency::make_free_balance_be(&treasury_account, T::Currency::minimum_balance());
+		TreasuryAccount::<T>::put(treasury_account);
+
+		let session_numbers = Pallet::<T>::session_numbers_from_reveals(game_id, session_id).ok_or("No reveals")?;
+		let losing_numbers = guess_numbers::<T>((1..=100).filter(|number| !session_numbers.contains(number)).take(6).collect())?;
+
+		let stake = T::MaxBet::get();
+		for index in 0 .. b + w {
+			let bet = Bet {
+				account_id: account("bettor", index, SEED),
+				guess_numbers: if index < w { session_numbers.clone() } else { losing_numbers.clone() },
+				bet: stake,
+			};
+			Bets::<T>::insert((game_id, session_id), index, bet);
+		}
+		let pot = stake.saturating_mul(BalanceOf::<T>::from(b + w));
+		BetCount::<T>::insert(game_id, session_id, b + w);
+		SessionPot::<T>::insert(game_id, session_id, pot);
+		T::Currency::make_free_balance_be(&Pallet::<T>::account_id(), pot.saturating_add(T::Currency::minimum_balance()));
+
+		Pallet::<T>::close_the_session(game_id)?;
+
+		let submissions: Vec<(T::AccountId, GuessNumbersOf<T>)> = authorities.iter()
+			.skip(1)
+			.take(Pallet::<T>::finalisation_quorum() as usize - 1)
+			.map(|authority| (authority.clone(), session_numbers.clone()))
+			.collect();
+		SessionNumbersSubmissions::<T>::insert(game_id, session_id, submissions);
+
+		let payload = SessionNumbersPayload {
+			public: signer,
+			block_number: <frame_system::Module<T>>::block_number(),
+			game_id,
+			session_id,
+			session_numbers,
+		};
+		let signature: T::Signature = zeroed()?;
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(
+			Pallet::<T>::sessions(game_id, session_id).map(|session_info| session_info.status),
+			Some(SessionStatus::Finalised)
+		);
+	}
+
+	// `t` jackpot tickets of the caller.
+	claim_reward {
+		let t in 1 .. T::MaxTicketsPerAccount::get();
+
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+
+		let numbers = guess_numbers::<T>((1..=6).collect())?;
+		let tickets = (0 .. t)
+			.map(|_| Bet { account_id: caller.clone(), guess_numbers: numbers.clone(), bet: T::MaxBet::get() })
+			.collect();
+		Pallet::<T>::place_bets(game_id, &caller, tickets)?;
+
+		Pallet::<T>::close_the_session(game_id)?;
+		Pallet::<T>::finalize_session_with_numbers(game_id, session_id, numbers)?;
+	}: _(RawOrigin::Signed(caller.clone()), game_id, session_id)
+	verify {
+		assert!(!Pallet::<T>::claimed_rewards((game_id, session_id), &caller).is_zero());
+	}
+
+	// `n` bets of an expired session, all refunded by the call, which then rolls the rest of the pot over.
+	refund_expired {
+		let n in 1 .. T::RefundBatchSize::get();
+
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+		let stake = T::MaxBet::get();
+
+		for index in 0 .. n {
+			let account_id: T::AccountId = account("bettor", index, SEED);
+			T::Currency::make_free_balance_be(&account_id, T::Currency::minimum_balance());
+			Bets::<T>::insert((game_id, session_id), index, Bet {
+				account_id,
+				guess_numbers: guess_numbers::<T>((1..=6).collect())?,
+				bet: stake,
+			});
+		}
+		let pot = stake.saturating_mul(BalanceOf::<T>::from(n));
+		BetCount::<T>::insert(game_id, session_id, n);
+		SessionPot::<T>::insert(game_id, session_id, pot);
+		T::Currency::make_free_balance_be(&Pallet::<T>::account_id(), pot.saturating_add(T::Currency::minimum_balance()));
+
+		Pallet::<T>::close_the_session(game_id)?;
+		Pallet::<T>::abandon_session(game_id, session_id, SessionStatus::Expired);
+
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), game_id, session_id, n)
+	verify {
+		assert!(!Pallet::<T>::expired_sessions().contains(&(game_id, session_id)));
+	}
+
+	// `b` tickets of `b` accounts, each holding a deposit.
+	prune_session {
+		let b in 1 .. T::MaxTicketsPerSession::get();
+
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+		let deposit = T::BetDeposit::get();
+		let stake = T::MaxBet::get();
+
+		for index in 0 .. b {
+			let account_id: T::AccountId = account("bettor", index, SEED);
+			T::Currency::make_free_balance_be(&account_id, deposit.saturating_add(T::Currency::minimum_balance()));
+			T::Currency::reserve(&account_id, deposit)?;
+			BetDeposits::<T>::insert((game_id, session_id), &account_id, deposit);
+			let tickets: BoundedVec<u32, T::MaxTicketsPerAccount> = vec![index].try_into().map_err(|_| "MaxTicketsPerAccount is zero")?;
+			AccountTickets::<T>::insert((game_id, session_id), &account_id, tickets);
+			Bets::<T>::insert((game_id, session_id), index, Bet {
+				account_id,
+				guess_numbers: guess_numbers::<T>((1..=6).collect())?,
+				bet: stake,
+			});
+		}
+		BetCount::<T>::insert(game_id, session_id, b);
+
+		Pallet::<T>::close_the_session(game_id)?;
+		Pallet::<T>::finalize_session_with_numbers(game_id, session_id, guess_numbers::<T>((7..=12).collect())?)?;
+		// Skip ahead past the retention and claim periods.
+		let periods = T::RetentionPeriod::get().max(T::ClaimPeriod::get());
+		SessionId::<T>::insert(game_id, session_id.saturating_add(periods).saturating_add(1));
+
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), game_id, session_id)
+	verify {
+		assert_eq!(Pallet::<T>::bet_count(game_id, session_id), 0);
+	}
+
+	add_nft_hash_to_winner {
+		let n in 1 .. MAX_PENDING_WINNERS;
+
+		let class_id = NftModule::<T>::create_class(&account("owner", 0, SEED), Vec::new(), ())?;
+		ClassId::<T>::put(class_id);
+
+		let mut pending_winners: Vec<NFTRequestDataOf<T>> = (0 .. n).map(|index| NFTRequestData {
+			winner_account: account("winner", index, SEED),
+			reward: T::MaxBet::get(),
+			score: 6,
+			score_out_of: 6,
+			game_id: 0,
+			session_id: 0,
+		}).collect();
+		pending_winners.sort();
+		let nft_request_data = pending_winners[(n - 1) as usize].clone();
+		PendingWinnersNFT::<T>::put(&pending_winners);
+
+		let payload = NftHashPayload {
+			public: zeroed()?,
+			nft_hash: b"QmWinnerNftHash".to_vec(),
+		};
+		let signature: T::Signature = zeroed()?;
+	}: _(RawOrigin::None, nft_request_data.clone(), payload, signature)
+	verify {
+		assert!(!Pallet::<T>::pending_winners_nft().contains(&nft_request_data));
+	}
+
+	set_ticket_price {
+		let game_id = create_game::<T>()?;
+		let new_price = T::MaxBet::get();
+	}: _(RawOrigin::Root, game_id, new_price)
+	verify {
+		assert_eq!(Pallet::<T>::games(game_id).map(|game| game.ticket_price), Some(new_price));
+	}
+
+	commit_session_secret {
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+		let (signer, authorities) = set_authorities::<T>(1)?;
+
+		let payload = CommitPayload {
+			public: signer,
+			game_id,
+			session_id,
+			commitment: T::Hashing::hash_of(&[7u8; 32]),
+		};
+		let signature: T::Signature = zeroed()?;
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert!(Pallet::<T>::commitments((game_id, session_id), &authorities[0]).is_some());
+	}
+
+	// The last of `a` authorities to reveal.
+	reveal_session_secret {
+		let a in 1 .. MAX_AUTHORITIES;
+
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+		let (signer, authorities) = set_authorities::<T>(a)?;
+		for (index, authority) in authorities.iter().enumerate().skip(1) {
+			Reveals::<T>::append(game_id, session_id, (authority.clone(), [index as u8; 32]));
+		}
+		let secret: SecretType = [0; 32];
+		Commitments::<T>::insert((game_id, session_id), &authorities[0], T::Hashing::hash_of(&secret));
+		Pallet::<T>::close_the_session(game_id)?;
+
+		let payload = RevealPayload { public: signer, game_id, session_id, secret };
+		let signature: T::Signature = zeroed()?;
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(Pallet::<T>::reveals(game_id, session_id).len() as u32, a);
+	}
+
+	set_prize_table {
+		let t in 1 .. T::MaxPickCount::get();
+
+		let game_id = Pallet::<T>::do_create_game(widest_game::<T>())?;
+		let prize_table = prize_table::<T>(t);
+	}: _(RawOrigin::Root, game_id, prize_table.clone())
+	verify {
+		assert_eq!(Pallet::<T>::games(game_id).map(|game| game.prize_table), Some(prize_table));
+	}
+
+	set_operator_fee {
+		let treasury_account: T::AccountId = account("treasury", 0, SEED);
+	}: _(RawOrigin::Root, Percent::from_percent(10), Percent::from_percent(50), Some(treasury_account.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::treasury_account(), Some(treasury_account));
+	}
+
+	set_draw_mode {
+	}: _(RawOrigin::Root, DrawMode::OnChain)
+	verify {
+		assert_eq!(Pallet::<T>::draw_mode(), DrawMode::OnChain);
+	}
+
+	set_session_length {
+		let game_id = create_game::<T>()?;
+		let session_length = T::BlockNumber::from(100u32);
+	}: _(RawOrigin::Root, game_id, session_length)
+	verify {
+		assert_eq!(Pallet::<T>::games(game_id).map(|game| game.session_length), Some(session_length));
+	}
+
+	set_session_schedule {
+		let game_id = create_game::<T>()?;
+		let session_schedule = SessionSchedule::WallClock { period: 86_400, offset: 72_000 };
+	}: _(RawOrigin::Root, game_id, session_schedule)
+	verify {
+		assert_eq!(Pallet::<T>::games(game_id).map(|game| game.schedule), Some(session_schedule));
+	}
+
+	pause {
+	}: _(RawOrigin::Root, PauseTarget::Drawing)
+	verify {
+		assert!(Pallet::<T>::is_paused(PauseTarget::Drawing));
+	}
+
+	unpause {
+		Paused::<T>::insert(PauseTarget::Drawing, true);
+	}: _(RawOrigin::Root, PauseTarget::Drawing)
+	verify {
+		assert!(!Pallet::<T>::is_paused(PauseTarget::Drawing));
+	}
+
+	resolve_session_conflict {
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+		ConflictedSessions::<T>::insert(game_id, session_id, true);
+	}: _(RawOrigin::Root, game_id, session_id)
+	verify {
+		assert!(!Pallet::<T>::conflicted_sessions(game_id, session_id));
+	}
+
+	// A closed session holding the commitments of `a` authorities.
+	cancel_session {
+		let a in 1 .. MAX_AUTHORITIES;
+
+		let game_id = create_game::<T>()?;
+		let session_id = Pallet::<T>::session_id(game_id);
+		let (_, authorities) = set_authorities::<T>(a)?;
+		for (index, authority) in authorities.iter().enumerate() {
+			Commitments::<T>::insert((game_id, session_id), authority, T::Hashing::hash_of(&[index as u8; 32]));
+		}
+		Pallet::<T>::close_the_session(game_id)?;
+	}: _(RawOrigin::Root, game_id, session_id)
+	verify {
+		assert_eq!(
+			Pallet::<T>::sessions(game_id, session_id).map(|session_info| session_info.status),
+			Some(SessionStatus::Cancelled)
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn create_game() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_game::<Test>());
+		});
+	}
+
+	#[test]
+	fn add_new_bet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_new_bet::<Test>());
+		});
+	}
+
+	#[test]
+	fn finalize_the_session() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_finalize_the_session::<Test>());
+		});
+	}
+
+	#[test]
+	fn claim_reward() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_reward::<Test>());
+		});
+	}
+
+	#[test]
+	fn refund_expired() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_refund_expired::<Test>());
+		});
+	}
+
+	#[test]
+	fn prune_session() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_prune_session::<Test>());
+		});
+	}
+
+	#[test]
+	fn add_nft_hash_to_winner() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_nft_hash_to_winner::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_ticket_price() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_ticket_price::<Test>());
+		});
+	}
+
+	#[test]
+	fn commit_session_secret() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_commit_session_secret::<Test>());
+		});
+	}
+
+	#[test]
+	fn reveal_session_secret() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_reveal_session_secret::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_prize_table() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_prize_table::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_operator_fee() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_operator_fee::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_draw_mode() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_draw_mode::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_session_length() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_session_length::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_session_schedule() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_session_schedule::<Test>());
+		});
+	}
+
+	#[test]
+	fn pause() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_pause::<Test>());
+		});
+	}
+
+	#[test]
+	fn unpause() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unpause::<Test>());
+		});
+	}
+
+	#[test]
+	fn resolve_session_conflict() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_resolve_session_conflict::<Test>());
+		});
+	}
+
+	#[test]
+	fn cancel_session() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_session::<Test>());
+		});
+	}
+}
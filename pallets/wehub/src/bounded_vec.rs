@@ -0,0 +1,120 @@
+//! A `Vec` whose length is bounded by a `Get<u32>`, checked when it is built and when it is decoded,
+//! so an oversized ticket is rejected before it is ever stored or iterated over.
+
+use codec::{Compact, Decode, Encode, EncodeLike, Error, Input, Output};
+use frame_support::traits::Get;
+use sp_std::{convert::TryFrom, fmt, marker::PhantomData, ops::Deref, prelude::*};
+
+pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
+
+impl<T, S: Get<u32>> BoundedVec<T, S> {
+	/// The maximum number of elements.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Appends `element` unless the vector is already full.
+	pub fn try_push(&mut self, element: T) -> Result<(), ()> {
+		if self.0.len() >= Self::bound() {
+			return Err(());
+		}
+
+		self.0.push(element);
+		Ok(())
+	}
+}
+
+impl<T, S> BoundedVec<T, S> {
+	pub fn into_inner(self) -> Vec<T> {
+		self.0
+	}
+}
+
+impl<T, S: Get<u32>> TryFrom<Vec<T>> for BoundedVec<T, S> {
+	type Error = ();
+
+	fn try_from(inner: Vec<T>) -> Result<Self, ()> {
+		if inner.len() > Self::bound() {
+			return Err(());
+		}
+
+		Ok(BoundedVec(inner, PhantomData))
+	}
+}
+
+impl<T, S> Deref for BoundedVec<T, S> {
+	type Target = Vec<T>;
+
+	fn deref(&self) -> &Vec<T> {
+		&self.0
+	}
+}
+
+impl<T, S> Default for BoundedVec<T, S> {
+	fn default() -> Self {
+		BoundedVec(Vec::new(), PhantomData)
+	}
+}
+
+impl<T: Clone, S> Clone for BoundedVec<T, S> {
+	fn clone(&self) -> Self {
+		BoundedVec(self.0.clone(), PhantomData)
+	}
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedVec<T, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<T: Eq, S> Eq for BoundedVec<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for BoundedVec<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<T: Encode, S> Encode for BoundedVec<T, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+}
+
+impl<T: Encode, S> EncodeLike for BoundedVec<T, S> {}
+
+impl<T: Decode, S: Get<u32>> Decode for BoundedVec<T, S> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let length = <Compact<u32>>::decode(input)?.0;
+		if length > S::get() {
+			return Err("BoundedVec exceeds its bound".into());
+		}
+
+		let inner = (0..length)
+			.map(|_| T::decode(input))
+			.collect::<Result<Vec<T>, Error>>()?;
+
+		Ok(BoundedVec(inner, PhantomData))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: serde::Serialize, S> serde::Serialize for BoundedVec<T, S> {
+	fn serialize<D: serde::Serializer>(&self, serializer: D) -> Result<D::Ok, D::Error> {
+		self.0.serialize(serializer)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de, T: serde::Deserialize<'de>, S: Get<u32>> serde::Deserialize<'de> for BoundedVec<T, S> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let inner = Vec::<T>::deserialize(deserializer)?;
+
+		Self::try_from(inner).map_err(|()| serde::de::Error::custom("BoundedVec exceeds its bound"))
+	}
+}
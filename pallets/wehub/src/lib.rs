@@ -1,17 +1,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{
-	decl_module,
-	decl_storage,
-	decl_event,
-	decl_error,
 	codec::{
 		Encode,
 		Decode,
 	},
 	traits::{
 		Vec,
+		Get,
 		Currency,
+		ReservableCurrency,
+		Randomness,
+		UnixTime,
 		ExistenceRequirement::KeepAlive,
 	},
 	dispatch::{
@@ -19,6 +19,8 @@ use frame_support::{
 		DispatchResult,
 	},
 	debug,
+	ensure,
+	weights::Weight,
 	unsigned::{
 		ValidateUnsigned,
 	},
@@ -26,6 +28,7 @@ use frame_support::{
 use frame_system::{
 	ensure_signed,
 	ensure_none,
+	ensure_root,
 	offchain::{
 		AppCrypto,
 		CreateSignedTransaction,
@@ -40,12 +43,16 @@ use sp_runtime::{
 	RandomNumberGenerator,
 	traits::{
 		BlakeTwo256,
+		Hash as HashT,
 		IdentifyAccount,
 		AccountIdConversion,
 		Saturating,
+		Zero,
+		One,
 	},
 	offchain as rt_offchain,
 	offchain::{
+		storage::StorageValueRef,
 		storage_lock::{StorageLock, BlockAndTime},
 	},
 	RuntimeDebug,
@@ -64,10 +71,18 @@ use sp_core::{
 		KeyTypeId,
 	},
 };
-use sp_std::convert::{TryInto};
+use sp_std::{
+	vec,
+	convert::{TryInto},
+	collections::btree_set::BTreeSet,
+};
 use codec::{alloc::string::{ToString, String}};
-use sp_arithmetic::Percent;
+use sp_arithmetic::{Percent, Perbill};
 use orml_nft::Module as NftModule;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+pub use pallet::*;
 
 #[cfg(test)]
 mod mock;
@@ -75,346 +90,1942 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-mod group_by;
-pub use group_by::{GroupByTrait};
+mod bounded_vec;
+pub use bounded_vec::BoundedVec;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub mod migrations;
+
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"whub");
+
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{app_crypto::{app_crypto, sr25519}, MultiSigner, MultiSignature};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct TestAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TestAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+const SESSION_IN_BLOCKS: u32 = 5;
+/// `InvalidTransaction::Custom` code for unsigned calls refused while paused.
+const PAUSED_TX_ERROR: u8 = 1;
+const UNSIGNED_TX_PRIORITY: u64 = 100;
+const PALLET_ID: ModuleId = ModuleId(*b"JackPot!");
+
+pub type GameId = u32;
+pub type SessionIdType = u128;
+type GameSessionId = (GameId, SessionIdType);
+pub type GuessNumbersOf<T> = BoundedVec<u8, <T as Config>::MaxPickCount>;
+type SecretType = [u8; 32];
+pub type Winners<AccountId, Balance, GuessNumbers> = Vec<(Bet<AccountId, Balance, GuessNumbers>, u8)>;
+type WinnersOf<T> = Winners<<T as frame_system::Config>::AccountId, BalanceOf<T>, GuessNumbersOf<T>>;
+pub type NFTHash = Vec<u8>;
+
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, PartialOrd, Ord)]
+pub struct NFTRequestData<AccountId, Balance> {
+	winner_account: AccountId,
+	reward: Balance,
+	score: u8,
+	score_out_of: u8,
+	game_id: GameId,
+	session_id: SessionIdType,
+}
+
+type NFTRequestDataOf<T> = NFTRequestData<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Bet<AccountId, Balance, GuessNumbers> {
+	pub account_id: AccountId,
+	pub guess_numbers: GuessNumbers,
+	pub bet: Balance,
+}
+
+type BetOf<T> = Bet<<T as frame_system::Config>::AccountId, BalanceOf<T>, GuessNumbersOf<T>>;
+
+/// A session finalised in a block, read back from the events of the block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SessionOutcome<AccountId, Balance, GuessNumbers> {
+	pub game_id: GameId,
+	pub session_id: SessionIdType,
+	pub session_numbers: GuessNumbers,
+	pub winners: Winners<AccountId, Balance, GuessNumbers>,
+	/// The rewards the winning tickets can claim, in ticket order.
+	pub payouts: Vec<(AccountId, Balance)>,
+}
+
+type SessionOutcomeOf<T> = SessionOutcome<<T as frame_system::Config>::AccountId, BalanceOf<T>, GuessNumbersOf<T>>;
+
+/// The storage layouts of the pallet, in the order runtime upgrades migrate through them.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	/// The layout written by the `decl_storage` pallet, before storage versioning.
+	V1_0_0,
+	/// Games, bounded tickets and bets stored per ticket.
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// How the numbers of a closed session are drawn.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum DrawMode {
+	/// Authorities commit and reveal secrets through their offchain workers.
+	Offchain,
+	/// Numbers are drawn from `Config::Randomness` a few blocks after the session closes.
+	OnChain,
+}
+
+impl Default for DrawMode {
+	fn default() -> Self {
+		DrawMode::Offchain
+	}
+}
+
+/// When the open session of a game closes.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SessionSchedule {
+	/// Sessions close every `session_length` blocks.
+	Blocks,
+	/// Sessions close at the first block after every `offset + k * period` seconds since the unix epoch.
+	/// Daily at 20:00 UTC is `{ period: 86_400, offset: 72_000 }`.
+	WallClock { period: u64, offset: u64 },
+}
+
+impl Default for SessionSchedule {
+	fn default() -> Self {
+		SessionSchedule::Blocks
+	}
+}
+
+/// A part of the pallet that root can pause.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PauseTarget {
+	/// `add_new_bet` and `add_new_bets`.
+	Betting,
+	/// Closing sessions, drawing or submitting their numbers, and expiring overdue sessions along with
+	/// their automatic refunds. `refund_expired` keeps working for sessions that already expired.
+	Drawing,
+	/// Minting NFTs for winners.
+	NftMinting,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SessionStatus {
+	Open,
+	Closed,
+	Finalised,
+	Expired,
+	Cancelled,
+}
+
+/// The lifecycle record of a session, kept so its history can be read without replaying events.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SessionInfo<BlockNumber, Balance, GuessNumbers> {
+	pub status: SessionStatus,
+	pub start_block: BlockNumber,
+	pub close_block: Option<BlockNumber>,
+	pub session_numbers: Option<GuessNumbers>,
+	pub total_pot: Balance,
+	pub fee: Balance,
+	/// What each prize tier pays out to the tickets claiming from it.
+	pub tiers: Vec<TierResult<Balance>>,
+	/// The total of the rewards won in the session.
+	pub rewards: Balance,
+	/// The part of `rewards` claimed so far.
+	pub claimed: Balance,
+}
+
+impl<BlockNumber, Balance: Zero, GuessNumbers> SessionInfo<BlockNumber, Balance, GuessNumbers> {
+	fn open(start_block: BlockNumber) -> Self {
+		SessionInfo {
+			status: SessionStatus::Open,
+			start_block,
+			close_block: None,
+			session_numbers: None,
+			total_pot: Zero::zero(),
+			fee: Zero::zero(),
+			tiers: Vec::new(),
+			rewards: Zero::zero(),
+			claimed: Zero::zero(),
+		}
+	}
+}
+
+type SessionInfoOf<T> = SessionInfo<<T as frame_system::Config>::BlockNumber, BalanceOf<T>, GuessNumbersOf<T>>;
+
+/// A row of the prize table. A ticket belongs to the tier with the highest `min_hits` it reaches.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PrizeTier<Balance> {
+	/// The minimum number of hits a ticket needs to win in this tier.
+	pub min_hits: u8,
+	/// The share of the session's reward pot allocated to this tier.
+	pub percentage: Percent,
+	/// Pays every winner of the tier this amount instead of splitting the share by stake.
+	/// A winner never gets more than an equal split of the tier's share.
+	pub fixed_prize: Option<Balance>,
+}
+
+type PrizeTierOf<T> = PrizeTier<BalanceOf<T>>;
+
+/// The outcome of a prize tier in a finalised session, from which its winners' rewards are computed when they claim.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TierResult<Balance> {
+	pub min_hits: u8,
+	/// The number of winning tickets in the tier.
+	pub winners: u32,
+	/// The total stake of the winning tickets, which splits `share` between them.
+	pub winning_stake: Balance,
+	/// The part of the reward pot allocated to the tier.
+	pub share: Balance,
+	/// The reward of every winning ticket when the tier pays a fixed prize.
+	pub fixed_prize: Option<Balance>,
+}
+
+type TierResultOf<T> = TierResult<BalanceOf<T>>;
+
+/// The 3 hits → 3%, 4 → 7%, 5 → 15%, 6 → 75% split of the whole reward pot.
+pub fn default_prize_table<Balance>() -> Vec<PrizeTier<Balance>> {
+	[(3, 3), (4, 7), (5, 15), (6, 75)].iter()
+		.map(|&(min_hits, percentage)| PrizeTier {
+			min_hits,
+			percentage: Percent::from_percent(percentage),
+			fixed_prize: None,
+		})
+		.collect()
+}
+
+/// A lottery game. Every game has its own sessions, pot and rollover.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Game<BlockNumber, Balance> {
+	/// The number of distinct numbers on a ticket and in a draw.
+	pub pick_count: u8,
+	/// The lowest number that can be picked.
+	pub min_number: u8,
+	/// The highest number that can be picked.
+	pub max_number: u8,
+	/// The price of a ticket, which is also the minimum stake of a bet.
+	pub ticket_price: Balance,
+	/// The number of blocks in a session of `SessionSchedule::Blocks`.
+	pub session_length: BlockNumber,
+	pub schedule: SessionSchedule,
+	pub prize_table: Vec<PrizeTier<Balance>>,
+}
+
+type GameOf<T> = Game<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+/// The original 6 of 1..=10 game, closing every 5 blocks with the default prize table.
+pub fn classic_game<BlockNumber: From<u32>, Balance>(ticket_price: Balance) -> Game<BlockNumber, Balance> {
+	Game {
+		pick_count: 6,
+		min_number: 1,
+		max_number: 10,
+		ticket_price,
+		session_length: BlockNumber::from(SESSION_IN_BLOCKS),
+		schedule: SessionSchedule::Blocks,
+		prize_table: default_prize_table(),
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SessionNumbersPayload<Public, BlockNumber, GuessNumbers> {
+	public: Public,
+	block_number: BlockNumber,
+	game_id: GameId,
+	session_id: SessionIdType,
+	session_numbers: GuessNumbers,
+}
+
+impl<T: SigningTypes, GuessNumbers: Encode> SignedPayload<T> for SessionNumbersPayload<T::Public, T::BlockNumber, GuessNumbers> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CommitPayload<Public, Hash> {
+	public: Public,
+	game_id: GameId,
+	session_id: SessionIdType,
+	commitment: Hash,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for CommitPayload<T::Public, T::Hash> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RevealPayload<Public> {
+	public: Public,
+	game_id: GameId,
+	session_id: SessionIdType,
+	secret: SecretType,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for RevealPayload<T::Public> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct NftHashPayload<Public> {
+	public: Public,
+	nft_hash: NFTHash,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for NftHashPayload<T::Public> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> + orml_nft::Config<TokenData = NFTHash, ClassData = ()> {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		type Call: From<Call<Self>>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The maximum number of games root can create.
+		#[pallet::constant]
+		type MaxGames: Get<u32>;
+
+		/// The maximum pick count of a game, which bounds the numbers on every ticket.
+		#[pallet::constant]
+		type MaxPickCount: Get<u32>;
+
+		/// The maximum stake of a single bet.
+		#[pallet::constant]
+		type MaxBet: Get<BalanceOf<Self>>;
+
+		/// The maximum number of tickets a single account can buy in one session.
+		#[pallet::constant]
+		type MaxTicketsPerAccount: Get<u32>;
+
+		/// The number of blocks after a session closes during which authorities can reveal their secrets.
+		#[pallet::constant]
+		type RevealPeriod: Get<Self::BlockNumber>;
+
+		/// The number of authorities that have to submit identical session numbers before a session is finalised.
+		/// It is capped by the size of the authority set.
+		#[pallet::constant]
+		type FinalisationThreshold: Get<u32>;
+
+		/// The on-chain randomness source used by `DrawMode::OnChain`.
+		type Randomness: Randomness<Self::Hash>;
+
+		/// The wall clock used by `SessionSchedule::WallClock`.
+		type UnixTime: UnixTime;
+
+		/// The number of blocks between closing a session and drawing its numbers in `DrawMode::OnChain`,
+		/// so the randomness used for the draw is not known yet when the last bets are placed.
+		#[pallet::constant]
+		type OnChainDrawDelay: Get<Self::BlockNumber>;
+
+		/// The number of sessions closed after a session during which its winners can claim their rewards.
+		/// Unclaimed rewards go back to the rollover afterwards.
+		#[pallet::constant]
+		type ClaimPeriod: Get<SessionIdType>;
+
+		/// The number of blocks after its close within which a session has to be finalised.
+		/// Afterwards it expires and its bets are refunded.
+		#[pallet::constant]
+		type FinalisationDeadline: Get<Self::BlockNumber>;
+
+		/// The maximum number of bets refunded in one block or one `refund_expired` call.
+		#[pallet::constant]
+		type RefundBatchSize: Get<u32>;
+
+		/// The maximum number of tickets in one session.
+		#[pallet::constant]
+		type MaxTicketsPerSession: Get<u32>;
+
+		/// The number of sessions the bets and missed reveals of a settled session are kept before they are pruned.
+		#[pallet::constant]
+		type RetentionPeriod: Get<SessionIdType>;
+
+		/// The deposit reserved for every ticket until its session is pruned. Zero disables it.
+		#[pallet::constant]
+		type BetDeposit: Get<BalanceOf<Self>>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(block_number: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads(3);
+			let drawing_paused = Self::is_paused(PauseTarget::Drawing);
+
+			for game_id in 0..Self::next_game_id() {
+				let scheduled_draw = Self::closed_not_finalised_session(game_id)
+					.and_then(|session_id| Self::on_chain_draw_at(game_id, session_id).map(|draw_at| (session_id, draw_at)));
+
+				weight = weight.saturating_add(T::DbWeight::get().reads(2));
+
+				if let Some((session_id, draw_at)) = scheduled_draw {
+					if block_number >= draw_at && !drawing_paused {
+						OnChainDrawAt::<T>::remove(game_id, session_id);
+
+						// The draw finalises the session, so it is charged like `finalize_the_session`.
+						let finalisation_weight = Self::finalisation_weight(game_id, session_id);
+						if let Err(error) = Self::draw_session_numbers_on_chain(game_id, session_id) {
+							debug::info!("--- on_initialize draw error: {:?}", error);
+						}
+
+						weight = weight
+							.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+							.saturating_add(finalisation_weight);
+					}
+				}
+
+				// A paused draw must not expire the sessions it holds back.
+				if !drawing_paused && Self::expire_overdue_session(game_id, block_number) {
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 8));
+				}
+			}
+
+			if let Some((game_id, session_id)) = Self::expired_sessions().first().cloned().filter(|_| !drawing_paused) {
+				let refunded = Self::refund_bets(game_id, session_id, T::RefundBatchSize::get());
+				weight = weight.saturating_add(T::WeightInfo::refund_expired(refunded));
+			}
+
+			weight
+		}
+
+		fn on_finalize(block_number: T::BlockNumber) {
+			if Self::is_paused(PauseTarget::Drawing) {
+				return;
+			}
+
+			for game_id in 0..Self::next_game_id() {
+				if let Some(game) = Self::games(game_id) {
+					if Self::session_due(game_id, &game, block_number) {
+						let _ = Self::close_the_session(game_id);
+					}
+				}
+			}
+		}
+
+		fn offchain_worker(block_number: T::BlockNumber) {
+			debug::RuntimeLogger::init();
+
+			for game_id in 0..Self::next_game_id() {
+				if Self::draw_mode() == DrawMode::Offchain {
+					if let Err(error) = Self::commit_session_secret_and_send(game_id, Self::session_id(game_id)) {
+						debug::info!("--- offchain_worker commit error: {}", error);
+					}
+				}
+
+				// Secrets are revealed for every pending session, but numbers are only submitted for the
+				// oldest one, so the sessions of a game are finalised in the order they were closed.
+				let closed_offchain_sessions = Self::pending_sessions(game_id).into_iter()
+					.filter(|session_id| Self::reveal_deadline(game_id, session_id).is_some());
+
+				for session_id in closed_offchain_sessions {
+					debug::info!("--- offchain_worker start block_number: {:?}, game_id: {}, session_id: {}", block_number, game_id, session_id);
+
+					let result = if !Self::reveal_phase_over(game_id, session_id) {
+						Self::reveal_session_secret_and_send(game_id, session_id)
+					} else if Self::closed_not_finalised_session(game_id) == Some(session_id) && !Self::is_paused(PauseTarget::Drawing) {
+						Self::generate_session_numbers_and_send(block_number, game_id, session_id)
+					} else {
+						Ok(())
+					};
+
+					if let Err(error) = result {
+						debug::info!("--- offchain_worker error: {}", error);
+					}
+				}
+			}
+
+			const LOCK_DURATION_IN_BLOCKS: u32 = 3;
+			let mut lock_nft = StorageLock::<BlockAndTime<Self>>::with_block_deadline(b"wehub_nft::lock", LOCK_DURATION_IN_BLOCKS);
+
+			let pending_winners_nft = Self::pending_winners_nft();
+			if pending_winners_nft.len() > 0 && !Self::is_paused(PauseTarget::NftMinting) {
+				if let Ok(_guard) = lock_nft.try_lock() {
+					Self::generafte_pending_winners_nft(pending_winners_nft);
+				}
+			}
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(T::WeightInfo::create_game(game.prize_table.len() as u32))]
+		pub fn create_game(origin: OriginFor<T>, game: GameOf<T>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let game_id = Self::do_create_game(game.clone())?;
+
+			Self::deposit_event(Event::GameCreated(game_id, game));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::add_new_bet())]
+		pub fn add_new_bet(origin: OriginFor<T>, game_id: GameId, guess_numbers: GuessNumbersOf<T>, bet: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let game = Self::game(game_id)?;
+			Self::ensure_valid_guess_numbers(&game, &guess_numbers)?;
+			ensure!(bet >= game.ticket_price, Error::<T>::BetBelowTicketPrice);
+			ensure!(bet <= T::MaxBet::get(), Error::<T>::BetAboveMaximum);
+
+			let new_bet = Bet {
+				account_id: account_id.clone(),
+				guess_numbers,
+				bet,
+			};
+
+			Self::place_bets(game_id, &account_id, vec![new_bet])?;
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::add_new_bet().saturating_mul(guess_numbers_list.len() as Weight))]
+		pub fn add_new_bets(origin: OriginFor<T>, game_id: GameId, guess_numbers_list: Vec<GuessNumbersOf<T>>) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let game = Self::game(game_id)?;
+			ensure!(!guess_numbers_list.is_empty(), Error::<T>::NoTickets);
+			for guess_numbers in guess_numbers_list.iter() {
+				Self::ensure_valid_guess_numbers(&game, guess_numbers)?;
+			}
+
+			let new_bets = guess_numbers_list.into_iter()
+				.map(|guess_numbers| Bet {
+					account_id: account_id.clone(),
+					guess_numbers,
+					bet: game.ticket_price,
+				})
+				.collect::<Vec<BetOf<T>>>();
+
+			Self::place_bets(game_id, &account_id, new_bets)?;
+
+			Ok(().into())
+		}
+
+		/// Pays the caller the rewards of their winning tickets in a finalised session.
+		/// Rewards can be claimed once, until `ClaimPeriod` sessions have closed after the session.
+		#[pallet::weight(T::WeightInfo::claim_reward(T::MaxTicketsPerAccount::get()))]
+		pub fn claim_reward(origin: OriginFor<T>, game_id: GameId, session_id: SessionIdType) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			ensure!(!Self::claim_period_over(game_id, session_id), Error::<T>::ClaimPeriodOver);
+			ensure!(!ClaimedRewards::<T>::contains_key((game_id, session_id), &account_id), Error::<T>::RewardAlreadyClaimed);
+
+			let pick_count = Self::game(game_id)?.pick_count;
+
+			let winning_tickets = Self::winning_tickets(game_id, session_id, &account_id);
+			let reward = winning_tickets.iter()
+				.fold(BalanceOf::<T>::zero(), |acc, (_, ticket_reward)| acc.saturating_add(*ticket_reward));
+			ensure!(!reward.is_zero(), Error::<T>::NoRewardToClaim);
+
+			T::Currency::transfer(&Self::account_id(), &account_id, reward, KeepAlive)?;
+			ClaimedRewards::<T>::insert((game_id, session_id), &account_id, reward);
+			Sessions::<T>::mutate(game_id, session_id, |session_info| {
+				if let Some(session_info) = session_info {
+					session_info.claimed = session_info.claimed.saturating_add(reward);
+				}
+			});
+
+			PendingWinnersNFT::<T>::mutate(|pending_winners_nft| {
+				for (hits, ticket_reward) in winning_tickets {
+					debug::info!("Account {:?} won {:?} $ by guessing {:?} numbers!", account_id, ticket_reward, hits);
+
+					pending_winners_nft.push(NFTRequestData {
+						winner_account: account_id.clone(),
+						reward: ticket_reward,
+						score: hits,
+						score_out_of: pick_count,
+						game_id,
+						session_id,
+					});
+				}
+			});
+
+			Self::deposit_event(Event::RewardClaimed(game_id, session_id, account_id, reward));
+
+			Ok(().into())
+		}
+
+		/// Refunds up to `max_refunds` bets of an expired session. Anyone can call it.
+		#[pallet::weight(T::WeightInfo::refund_expired((*max_refunds).min(T::RefundBatchSize::get())))]
+		pub fn refund_expired(origin: OriginFor<T>, game_id: GameId, session_id: SessionIdType, max_refunds: u32) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			ensure!(Self::expired_sessions().contains(&(game_id, session_id)), Error::<T>::SessionIsNotExpired);
+
+			Self::refund_bets(game_id, session_id, max_refunds.min(T::RefundBatchSize::get()));
+
+			Ok(().into())
+		}
+
+		/// Removes the bets of a settled session older than `RetentionPeriod` and releases their deposits.
+		/// Anyone can call it.
+		#[pallet::weight(T::WeightInfo::prune_session(Pallet::<T>::bet_count(*game_id, *session_id)))]
+		pub fn prune_session(origin: OriginFor<T>, game_id: GameId, session_id: SessionIdType) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			ensure!(Self::can_prune(game_id, session_id), Error::<T>::SessionCannotBePruned);
+
+			Self::prune_session_bets(game_id, session_id);
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_ticket_price())]
+		pub fn set_ticket_price(origin: OriginFor<T>, game_id: GameId, new_price: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(!new_price.is_zero(), Error::<T>::ZeroTicketPrice);
+
+			let mut game = Self::game(game_id)?;
+			game.ticket_price = new_price;
+			Games::<T>::insert(game_id, game);
+
+			Self::deposit_event(Event::TicketPriceChanged(game_id, new_price));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::add_nft_hash_to_winner(PendingWinnersNFT::<T>::decode_len().unwrap_or(0) as u32))]
+		pub fn add_nft_hash_to_winner(origin: OriginFor<T>, nft_request_data: NFTRequestDataOf<T>, payload: NftHashPayload<T::Public>, _singature: T::Signature) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			ensure!(!Self::is_paused(PauseTarget::NftMinting), Error::<T>::NftMintingPaused);
+
+			let mut pending_winners = Self::pending_winners_nft();
+
+			match pending_winners.iter().position(|pending_winner| pending_winner == &nft_request_data) {
+				Some(index) => {
+					let nft_token_id = NftModule::<T>::mint(&nft_request_data.winner_account, Self::class_id(), Vec::new(), payload.nft_hash.clone())?;
+
+					pending_winners.remove(index);
+					PendingWinnersNFT::<T>::put(pending_winners);
+
+					let nft_hash_string = String::from_utf8(payload.nft_hash).expect("--- invalid utf-8");
+					debug::info!("--- add_nft_hash_to_winner: nft_token_id: {:?}, account_id: {:?} / nft_hash: {}", nft_token_id, nft_request_data.winner_account, nft_hash_string);
+				},
+				None => {
+					return Err(Error::<T>::PendingWinnerDoesNotExist.into())
+				},
+			};
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::commit_session_secret())]
+		pub fn commit_session_secret(origin: OriginFor<T>, payload: CommitPayload<T::Public, T::Hash>, _singature: T::Signature) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let game_id = payload.game_id;
+			let session_id = payload.session_id;
+			let account_id = payload.public.into_account();
+
+			ensure!(Games::<T>::contains_key(game_id), Error::<T>::GameDoesNotExist);
+			ensure!(session_id == Self::session_id(game_id), Error::<T>::CommitPhaseOver);
+			ensure!(!Commitments::<T>::contains_key((game_id, session_id), &account_id), Error::<T>::AlreadyCommitted);
+
+			Commitments::<T>::insert((game_id, session_id), &account_id, payload.commitment);
+
+			Self::deposit_event(Event::SecretCommitted(game_id, session_id, account_id));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::reveal_session_secret(Authorities::<T>::decode_len().unwrap_or(0) as u32))]
+		pub fn reveal_session_secret(origin: OriginFor<T>, payload: RevealPayload<T::Public>, _singature: T::Signature) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let game_id = payload.game_id;
+			let session_id = payload.session_id;
+			let account_id = payload.public.into_account();
+
+			ensure!(Self::pending_sessions(game_id).contains(&session_id), Error::<T>::TryToRevealForSessionWhichIsNotClosed);
+			ensure!(!Self::reveal_deadline_passed(game_id, session_id), Error::<T>::RevealPhaseOver);
+			ensure!(!Self::has_revealed(game_id, session_id, &account_id), Error::<T>::AlreadyRevealed);
+
+			let commitment = Self::commitments((game_id, session_id), &account_id).ok_or(Error::<T>::CommitmentDoesNotExist)?;
+			ensure!(T::Hashing::hash_of(&payload.secret) == commitment, Error::<T>::InvalidReveal);
+
+			Reveals::<T>::append(game_id, session_id, (account_id.clone(), payload.secret));
+
+			Self::deposit_event(Event::SecretRevealed(game_id, session_id, account_id));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_prize_table(prize_table.len() as u32))]
+		pub fn set_prize_table(origin: OriginFor<T>, game_id: GameId, prize_table: Vec<PrizeTierOf<T>>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let mut game = Self::game(game_id)?;
+			Self::ensure_valid_prize_table(&prize_table, game.pick_count)?;
+
+			game.prize_table = prize_table.clone();
+			Games::<T>::insert(game_id, game);
+
+			Self::deposit_event(Event::PrizeTableChanged(game_id, prize_table));
+
+			Ok(().into())
+		}
+
+		/// Sets the share of every session pot taken as a fee, the part of the fee that goes to
+		/// `treasury_account`, and the treasury account itself. The rest is split between the authorities.
+		#[pallet::weight(T::WeightInfo::set_operator_fee())]
+		pub fn set_operator_fee(origin: OriginFor<T>, fee: Percent, treasury_share: Percent, treasury_account: Option<T::AccountId>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			OperatorFee::<T>::put(fee);
+			TreasuryShare::<T>::put(treasury_share);
+			TreasuryAccount::<T>::set(treasury_account.clone());
+
+			Self::deposit_event(Event::OperatorFeeChanged(fee, treasury_share, treasury_account));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_draw_mode())]
+		pub fn set_draw_mode(origin: OriginFor<T>, draw_mode: DrawMode) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			DrawModeSetting::<T>::put(draw_mode);
+
+			Self::deposit_event(Event::DrawModeChanged(draw_mode));
+
+			Ok(().into())
+		}
+
+		/// Changes the number of blocks in a session of the game. The open session keeps its length.
+		#[pallet::weight(T::WeightInfo::set_session_length())]
+		pub fn set_session_length(origin: OriginFor<T>, game_id: GameId, session_length: T::BlockNumber) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(!session_length.is_zero(), Error::<T>::InvalidSessionLength);
+
+			let mut game = Self::game(game_id)?;
+			game.session_length = session_length;
+			Games::<T>::insert(game_id, game);
+
+			Self::deposit_event(Event::SessionLengthChanged(game_id, session_length));
+
+			Ok(().into())
+		}
+
+		/// Switches the game between block and wall-clock sessions. The open session closes on the new schedule.
+		#[pallet::weight(T::WeightInfo::set_session_schedule())]
+		pub fn set_session_schedule(origin: OriginFor<T>, game_id: GameId, session_schedule: SessionSchedule) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let mut game = Self::game(game_id)?;
+			Self::ensure_valid_schedule(&session_schedule)?;
+
+			match session_schedule {
+				SessionSchedule::Blocks => {
+					let block_number = <frame_system::Module<T>>::block_number();
+					SessionCloseBlock::<T>::insert(game_id, block_number.saturating_add(game.session_length));
+					NextCloseTime::<T>::remove(game_id);
+				},
+				SessionSchedule::WallClock { period, offset } => {
+					NextCloseTime::<T>::insert(game_id, Self::next_wall_clock_close(period, offset));
+				},
+			}
+
+			game.schedule = session_schedule;
+			Games::<T>::insert(game_id, game);
+
+			Self::deposit_event(Event::SessionScheduleChanged(game_id, session_schedule));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, target: PauseTarget) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			Paused::<T>::insert(target, true);
+
+			Self::deposit_event(Event::Paused(target));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>, target: PauseTarget) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			Paused::<T>::remove(target);
+
+			Self::deposit_event(Event::Unpaused(target));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::resolve_session_conflict())]
+		pub fn resolve_session_conflict(origin: OriginFor<T>, game_id: GameId, session_id: SessionIdType) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(Self::conflicted_sessions(game_id, session_id), Error::<T>::SessionIsNotConflicted);
+
+			ConflictedSessions::<T>::remove(game_id, session_id);
+			SessionNumbersSubmissions::<T>::remove(game_id, session_id);
+
+			Self::deposit_event(Event::SessionConflictResolved(game_id, session_id));
+
+			Ok(().into())
+		}
+
+		/// Cancels a closed session that is not finalised yet. Its bets are refunded like those of an expired session.
+		#[pallet::weight(T::WeightInfo::cancel_session(Authorities::<T>::decode_len().unwrap_or(0) as u32))]
+		pub fn cancel_session(origin: OriginFor<T>, game_id: GameId, session_id: SessionIdType) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(Self::pending_sessions(game_id).contains(&session_id), Error::<T>::SessionIsNotClosed);
+
+			Self::abandon_session(game_id, session_id, SessionStatus::Cancelled);
+
+			debug::info!("--- Session cancelled: {} of game {}", session_id, game_id);
+			Self::deposit_event(Event::SessionCancelled(game_id, session_id));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(Pallet::<T>::finalisation_weight(payload.game_id, payload.session_id))]
+		pub fn finalize_the_session(origin: OriginFor<T>, payload: SessionNumbersPayload<T::Public, T::BlockNumber, GuessNumbersOf<T>>, _singature: T::Signature) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			ensure!(!Self::is_paused(PauseTarget::Drawing), Error::<T>::DrawingPaused);
+
+			let game_id = payload.game_id;
+			let session_id = payload.session_id;
+			let account_id = payload.public.clone().into_account();
+
+			ensure!(
+				Self::closed_not_finalised_session(game_id) == Some(session_id),
+				Error::<T>::TryToFinalizeTheSessionWhichIsNotClosed
+			);
+			ensure!(!Self::conflicted_sessions(game_id, session_id), Error::<T>::SessionHasConflictingNumbers);
+			ensure!(Self::reveal_phase_over(game_id, session_id), Error::<T>::RevealPhaseNotOver);
+
+			let session_numbers = Self::session_numbers_from_reveals(game_id, session_id).ok_or(Error::<T>::NoReveals)?;
+
+			let mut submissions = Self::session_numbers_submissions(game_id, session_id);
+			ensure!(
+				!submissions.iter().any(|(submitter, _)| submitter == &account_id),
+				Error::<T>::AlreadySubmittedSessionNumbers
+			);
+			submissions.push((account_id.clone(), payload.session_numbers.clone()));
+			SessionNumbersSubmissions::<T>::insert(game_id, session_id, &submissions);
+
+			// Every submission has to match the numbers drawn from the reveals, which also makes them identical
+			// to each other. On a mismatch the session stays closed until root resolves the conflict.
+			if submissions.iter().any(|(_, numbers)| *numbers != session_numbers) {
+				debug::info!("--- Conflicting session numbers from {:?}: {:?}", account_id, payload.session_numbers);
+				ConflictedSessions::<T>::insert(game_id, session_id, true);
+				Self::deposit_event(Event::SessionNumbersConflict(game_id, session_id, account_id, payload.session_numbers));
+				return Ok(().into());
+			}
+
+			Self::deposit_event(Event::SessionNumbersSubmitted(game_id, session_id, account_id));
+
+			if (submissions.len() as u32) < Self::finalisation_quorum() {
+				return Ok(().into());
+			}
+
+			SessionNumbersSubmissions::<T>::remove(game_id, session_id);
+			Self::record_missed_reveals(game_id, session_id);
+
+			Self::finalize_session_with_numbers(game_id, session_id, session_numbers)?;
+
+			Ok(().into())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::BlockNumber = "BlockNumber",
+		BalanceOf<T> = "Balance",
+		GuessNumbersOf<T> = "GuessNumbers",
+		GameOf<T> = "Game",
+		BetOf<T> = "Bet",
+		WinnersOf<T> = "Winners",
+		Vec<PrizeTierOf<T>> = "Vec<PrizeTier>",
+		Vec<T::AccountId> = "Vec<AccountId>",
+		Option<T::AccountId> = "Option<AccountId>"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		GameCreated(GameId, GameOf<T>),
+		NewBet(GameId, SessionIdType, BetOf<T>),
+		Winners(GameId, SessionIdType, WinnersOf<T>),
+		SessionResults(GameId, SessionIdType, GuessNumbersOf<T>, WinnersOf<T>),
+		RewardFeeForAuthority(T::AccountId, BalanceOf<T>),
+		TicketPriceChanged(GameId, BalanceOf<T>),
+		SecretCommitted(GameId, SessionIdType, T::AccountId),
+		SecretRevealed(GameId, SessionIdType, T::AccountId),
+		MissedReveals(GameId, SessionIdType, Vec<T::AccountId>),
+		SessionNumbersSubmitted(GameId, SessionIdType, T::AccountId),
+		SessionNumbersConflict(GameId, SessionIdType, T::AccountId, GuessNumbersOf<T>),
+		SessionConflictResolved(GameId, SessionIdType),
+		DrawModeChanged(DrawMode),
+		SessionLengthChanged(GameId, T::BlockNumber),
+		SessionScheduleChanged(GameId, SessionSchedule),
+		Paused(PauseTarget),
+		Unpaused(PauseTarget),
+		TierRolledOver(GameId, SessionIdType, u8, BalanceOf<T>),
+		UnallocatedRolledOver(GameId, SessionIdType, BalanceOf<T>),
+		RolledOver(GameId, SessionIdType, BalanceOf<T>),
+		PrizeTableChanged(GameId, Vec<PrizeTierOf<T>>),
+		FeeForTreasury(T::AccountId, BalanceOf<T>),
+		OperatorFeeChanged(Percent, Percent, Option<T::AccountId>),
+		RewardClaimed(GameId, SessionIdType, T::AccountId, BalanceOf<T>),
+		RewardsExpired(GameId, SessionIdType, BalanceOf<T>),
+		SessionExpired(GameId, SessionIdType),
+		SessionCancelled(GameId, SessionIdType),
+		BetRefunded(GameId, SessionIdType, T::AccountId, BalanceOf<T>),
+		SessionRefunded(GameId, SessionIdType),
+		SessionPruned(GameId, SessionIdType, u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		SessionIdOverflow,
+		TryToFinalizeTheSessionWhichIsNotClosed,
+		PendingWinnerDoesNotExist,
+		NftHttpFetchingError,
+		BetBelowTicketPrice,
+		BetAboveMaximum,
+		GuessNumberOutOfRange,
+		DuplicateGuessNumber,
+		NoTickets,
+		TooManyTicketsPerAccount,
+		CommitPhaseOver,
+		AlreadyCommitted,
+		TryToRevealForSessionWhichIsNotClosed,
+		RevealPhaseOver,
+		CommitmentDoesNotExist,
+		AlreadyRevealed,
+		InvalidReveal,
+		RevealPhaseNotOver,
+		NoReveals,
+		AlreadySubmittedSessionNumbers,
+		SessionHasConflictingNumbers,
+		SessionIsNotConflicted,
+		PrizeTableExceedsPot,
+		InvalidPrizeTierHits,
+		NoRewardToClaim,
+		SessionIsNotExpired,
+		SessionIsNotClosed,
+		TooManyTicketsInSession,
+		SessionCannotBePruned,
+		InvalidSessionLength,
+		InvalidSessionSchedule,
+		BettingPaused,
+		DrawingPaused,
+		NftMintingPaused,
+		GameDoesNotExist,
+		TooManyGames,
+		InvalidGameNumbers,
+		WrongNumberOfGuessNumbers,
+		ClaimPeriodOver,
+		RewardAlreadyClaimed,
+		ZeroTicketPrice,
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn class_id)]
+	pub(super) type ClassId<T: Config> = StorageValue<_, T::ClassId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_game_id)]
+	pub(super) type NextGameId<T> = StorageValue<_, GameId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn games)]
+	pub(super) type Games<T: Config> = StorageMap<_, Twox64Concat, GameId, GameOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn session_id)]
+	pub(super) type SessionId<T> = StorageMap<_, Twox64Concat, GameId, SessionIdType, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sessions)]
+	pub(super) type Sessions<T: Config> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, SessionInfoOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn session_close_block)]
+	pub(super) type SessionCloseBlock<T: Config> = StorageMap<_, Twox64Concat, GameId, T::BlockNumber, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_close_time)]
+	pub(super) type NextCloseTime<T> = StorageMap<_, Twox64Concat, GameId, u64>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_paused)]
+	pub(super) type Paused<T> = StorageMap<_, Twox64Concat, PauseTarget, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bets)]
+	pub(super) type Bets<T: Config> = StorageDoubleMap<_, Blake2_128Concat, GameSessionId, Twox64Concat, u32, BetOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bet_count)]
+	pub(super) type BetCount<T> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bet_deposits)]
+	pub(super) type BetDeposits<T: Config> = StorageDoubleMap<_, Blake2_128Concat, GameSessionId, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn session_pot)]
+	pub(super) type SessionPot<T: Config> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rollover)]
+	pub(super) type Rollover<T: Config> = StorageMap<_, Twox64Concat, GameId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn claimed_rewards)]
+	pub(super) type ClaimedRewards<T: Config> = StorageDoubleMap<_, Blake2_128Concat, GameSessionId, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::type_value]
+	pub(super) fn DefaultOperatorFee() -> Percent {
+		Percent::from_percent(10)
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn operator_fee)]
+	pub(super) type OperatorFee<T> = StorageValue<_, Percent, ValueQuery, DefaultOperatorFee>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn treasury_share)]
+	pub(super) type TreasuryShare<T> = StorageValue<_, Percent, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn treasury_account)]
+	pub(super) type TreasuryAccount<T: Config> = StorageValue<_, T::AccountId>;
+
+	/// The indices in `Bets` of the tickets an account bought in a session.
+	#[pallet::storage]
+	#[pallet::getter(fn account_ticket_indices)]
+	pub(super) type AccountTickets<T: Config> = StorageDoubleMap<_, Blake2_128Concat, GameSessionId, Blake2_128Concat, T::AccountId, BoundedVec<u32, T::MaxTicketsPerAccount>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_sessions)]
+	pub(super) type PendingSessions<T> = StorageMap<_, Twox64Concat, GameId, Vec<SessionIdType>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn expired_sessions)]
+	pub(super) type ExpiredSessions<T> = StorageValue<_, Vec<GameSessionId>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn refund_cursor)]
+	pub(super) type RefundCursor<T> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn commitments)]
+	pub(super) type Commitments<T: Config> = StorageDoubleMap<_, Blake2_128Concat, GameSessionId, Blake2_128Concat, T::AccountId, T::Hash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reveals)]
+	pub(super) type Reveals<T: Config> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, Vec<(T::AccountId, SecretType)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reveal_deadline)]
+	pub(super) type RevealDeadline<T: Config> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, T::BlockNumber>;
+
+	/// The authorities that did not reveal their secret for a session, kept for `RetentionPeriod` sessions.
+	#[pallet::storage]
+	#[pallet::getter(fn missed_reveals)]
+	pub(super) type MissedReveals<T: Config> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn session_numbers_submissions)]
+	pub(super) type SessionNumbersSubmissions<T: Config> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, Vec<(T::AccountId, GuessNumbersOf<T>)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn conflicted_sessions)]
+	pub(super) type ConflictedSessions<T> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn draw_mode)]
+	pub(super) type DrawModeSetting<T> = StorageValue<_, DrawMode, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn on_chain_draw_at)]
+	pub(super) type OnChainDrawAt<T: Config> = StorageDoubleMap<_, Twox64Concat, GameId, Blake2_128Concat, SessionIdType, T::BlockNumber>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_winners_nft)]
+	pub(super) type PendingWinnersNFT<T: Config> = StorageValue<_, Vec<NFTRequestDataOf<T>>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn authorities)]
+	pub(super) type Authorities<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The storage layout of the pallet. Chains started before it was tracked read as `V1_0_0`.
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub(super) type StorageVersion<T> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub games: Vec<GameOf<T>>,
+		pub operator_fee: Percent,
+		pub treasury_share: Percent,
+		pub treasury_account: Option<T::AccountId>,
+		pub draw_mode: DrawMode,
+		pub offchain_authorities: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				games: Vec::new(),
+				operator_fee: DefaultOperatorFee::get(),
+				treasury_share: Default::default(),
+				treasury_account: None,
+				draw_mode: Default::default(),
+				offchain_authorities: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			OperatorFee::<T>::put(self.operator_fee);
+			TreasuryShare::<T>::put(self.treasury_share);
+			TreasuryAccount::<T>::set(self.treasury_account.clone());
+			DrawModeSetting::<T>::put(self.draw_mode);
+			Authorities::<T>::put(&self.offchain_authorities);
+			StorageVersion::<T>::put(Releases::V2_0_0);
+
+			for game in self.games.iter() {
+				Pallet::<T>::do_create_game(game.clone()).expect("Invalid game in chain spec");
+			}
+
+			let class_id = NftModule::<T>::create_class(&Default::default(), Vec::new(), ()).expect("Cannot fail or invalid chain spec");
+			ClassId::<T>::put(class_id);
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match call {
+				Call::commit_session_secret(ref payload, ref signature) => {
+					let account_id = Self::authority_account_from_payload(payload, signature)?;
+					let game_session_id = (payload.game_id, payload.session_id);
+
+					if payload.session_id != Self::session_id(payload.game_id) || Commitments::<T>::contains_key(game_session_id, &account_id) {
+						return InvalidTransaction::Stale.into();
+					}
+
+					return ValidTransaction::with_tag_prefix("WeHub/validate_unsigned/commit_session_secret")
+						.priority(UNSIGNED_TX_PRIORITY)
+						.and_provides((account_id, game_session_id))
+						.longevity(5)
+						.propagate(true)
+						.build();
+				},
+				Call::reveal_session_secret(ref payload, ref signature) => {
+					let account_id = Self::authority_account_from_payload(payload, signature)?;
+
+					if Self::has_revealed(payload.game_id, payload.session_id, &account_id) {
+						return InvalidTransaction::Stale.into();
+					}
+
+					return ValidTransaction::with_tag_prefix("WeHub/validate_unsigned/reveal_session_secret")
+						.priority(UNSIGNED_TX_PRIORITY)
+						.and_provides((account_id, (payload.game_id, payload.session_id)))
+						.longevity(5)
+						.propagate(true)
+						.build();
+				},
+				Call::finalize_the_session(ref payload, ref signature) => {
+					if Self::is_paused(PauseTarget::Drawing) {
+						return InvalidTransaction::Custom(PAUSED_TX_ERROR).into();
+					}
+
+					let account_id = Self::authority_account_from_payload(payload, signature)?;
+
+					let already_submitted = Self::session_numbers_submissions(payload.game_id, payload.session_id).iter()
+						.any(|(submitter, _)| submitter == &account_id);
+					if already_submitted || Self::conflicted_sessions(payload.game_id, payload.session_id) {
+						return InvalidTransaction::Stale.into();
+					}
+
+					return ValidTransaction::with_tag_prefix("WeHub/validate_unsigned/finalize_the_session")
+						.priority(UNSIGNED_TX_PRIORITY)
+						.and_provides((account_id, payload.game_id, payload.session_id))
+						.longevity(5)
+						.propagate(true)
+						.build();
+				},
+				Call::add_nft_hash_to_winner(_ntf_request_data, ref payload, ref signature) => {
+					if Self::is_paused(PauseTarget::NftMinting) {
+						return InvalidTransaction::Custom(PAUSED_TX_ERROR).into();
+					}
+
+					let account_id = Self::authority_account_from_payload(payload, signature)?;
+
+					return ValidTransaction::with_tag_prefix("WeHub/validate_unsigned/add_nft_hash_to_winner")
+						.priority(UNSIGNED_TX_PRIORITY)
+						.and_provides(account_id)
+						.and_provides(&payload.nft_hash)
+						.longevity(5)
+						.propagate(true)
+						.build();
+				}
+				_ => return InvalidTransaction::Call.into(),
+			};
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		PALLET_ID.into_account()
+	}
+
+	pub fn pot() -> (T::AccountId, BalanceOf<T>) {
+			let account_id = Self::account_id();
+			let balance = T::Currency::free_balance(&account_id)
+				.saturating_sub(T::Currency::minimum_balance());
+
+			(account_id, balance)
+	}
+
+	/// The blocks left before the open session closes, when the game closes its sessions by block number.
+	pub fn blocks_until_close(game_id: GameId) -> Option<T::BlockNumber> {
+		match Self::games(game_id)?.schedule {
+			SessionSchedule::Blocks => Some(
+				Self::session_close_block(game_id).saturating_sub(<frame_system::Module<T>>::block_number())
+			),
+			SessionSchedule::WallClock { .. } => None,
+		}
+	}
+
+	/// The tickets of an account in a session, in the order they were bought.
+	pub fn account_tickets(game_id: GameId, session_id: SessionIdType, account_id: &T::AccountId) -> Vec<BetOf<T>> {
+		Self::account_ticket_indices((game_id, session_id), account_id)
+			.iter()
+			.filter_map(|index| Self::bets((game_id, session_id), index))
+			.collect()
+	}
+
+	/// The tickets of a finalised session that reached a prize tier, until its bets are pruned.
+	pub fn session_winners(game_id: GameId, session_id: SessionIdType) -> WinnersOf<T> {
+		Self::sessions(game_id, session_id)
+			.and_then(|session_info| {
+				let winners = Self::get_winners(&session_info.session_numbers?, Self::session_bets(game_id, session_id));
+
+				Some(winners.into_iter()
+					.filter(|(_, hits)| Self::ticket_tier(&session_info.tiers, *hits).is_some())
+					.collect())
+			})
+			.unwrap_or_default()
+	}
+
+	/// The reward an account can still claim from a session. Zero once it is claimed or the claim period is over.
+	pub fn claimable_reward(game_id: GameId, session_id: SessionIdType, account_id: &T::AccountId) -> BalanceOf<T> {
+		if Self::claim_period_over(game_id, session_id) || ClaimedRewards::<T>::contains_key((game_id, session_id), account_id) {
+			return Zero::zero();
+		}
+
+		Self::winning_tickets(game_id, session_id, account_id).iter()
+			.fold(BalanceOf::<T>::zero(), |acc, (_, ticket_reward)| acc.saturating_add(*ticket_reward))
+	}
+
+	/// The sessions finalised by `events`, which are the events of a single block.
+	pub fn session_outcomes(events: impl IntoIterator<Item = Event<T>>) -> Vec<SessionOutcomeOf<T>> {
+		events.into_iter()
+			.filter_map(|event| match event {
+				Event::SessionResults(game_id, session_id, session_numbers, winners) => {
+					let tiers = Self::sessions(game_id, session_id).map(|session_info| session_info.tiers).unwrap_or_default();
+					// Only tickets that reached a prize tier, like `session_winners`.
+					let winners: WinnersOf<T> = winners.into_iter()
+						.filter(|(_, hits)| Self::ticket_tier(&tiers, *hits).is_some())
+						.collect();
+					let payouts = winners.iter()
+						.filter_map(|(bet, hits)| {
+							Self::ticket_tier(&tiers, *hits).map(|tier| (bet.account_id.clone(), Self::ticket_reward(tier, bet.bet)))
+						})
+						.filter(|(_, reward)| !reward.is_zero())
+						.collect();
+
+					Some(SessionOutcome {
+						game_id,
+						session_id,
+						session_numbers,
+						winners,
+						payouts,
+					})
+				},
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn game(game_id: GameId) -> Result<GameOf<T>, DispatchError> {
+		Self::games(game_id).ok_or_else(|| Error::<T>::GameDoesNotExist.into())
+	}
+
+	/// Registers a game and opens its first session.
+	fn do_create_game(game: GameOf<T>) -> Result<GameId, DispatchError> {
+		Self::ensure_valid_game(&game)?;
+
+		let game_id = Self::next_game_id();
+		ensure!(game_id < T::MaxGames::get(), Error::<T>::TooManyGames);
+
+		let block_number = <frame_system::Module<T>>::block_number();
+		Sessions::<T>::insert(game_id, Self::session_id(game_id), SessionInfo::open(block_number));
+		SessionCloseBlock::<T>::insert(game_id, block_number.saturating_add(game.session_length));
+
+		Games::<T>::insert(game_id, game);
+		NextGameId::<T>::put(game_id + 1);
+
+		Ok(game_id)
+	}
+
+	/// Splits the session fee between the treasury and the authorities and returns the amount actually paid.
+	/// Without authorities their part goes to the treasury, and without a treasury it is kept in the pot.
+	fn distribute_fees(fees: BalanceOf<T>) -> BalanceOf<T> {
+		let authorities = Self::authorities();
+		let treasury_account = Self::treasury_account();
+
+		let treasury_fee = match (&treasury_account, authorities.is_empty()) {
+			(Some(_), true) => fees,
+			(Some(_), false) => Self::treasury_share() * fees,
+			(None, _) => BalanceOf::<T>::zero(),
+		};
+		let mut fees_paid = BalanceOf::<T>::zero();
+
+		if let Some(treasury_account) = treasury_account {
+			if T::Currency::transfer(&Self::account_id(), &treasury_account, treasury_fee, KeepAlive).is_ok() {
+				debug::info!("--- Fee for treasury: {:?}, {:?} $", treasury_account, treasury_fee);
+				fees_paid = fees_paid.saturating_add(treasury_fee);
+				Self::deposit_event(Event::FeeForTreasury(treasury_account, treasury_fee));
+			}
+		}
+
+		if authorities.is_empty() {
+			return fees_paid;
+		}
+
+		let reward_fee_per_authority = fees.saturating_sub(treasury_fee) / BalanceOf::<T>::from(authorities.len() as u32);
+
+		for authority in authorities {
+			if T::Currency::transfer(&Self::account_id(), &authority, reward_fee_per_authority, KeepAlive).is_ok() {
+				debug::info!("--- Reward for authority: {:?}, {:?} $", authority, reward_fee_per_authority);
+				fees_paid = fees_paid.saturating_add(reward_fee_per_authority);
+				Self::deposit_event(Event::RewardFeeForAuthority(authority, reward_fee_per_authority));
+			}
+		}
+
+		fees_paid
+	}
+
+	/// Sums up the winning tickets of a tier. Their rewards are paid out when claimed.
+	fn tier_result(tier: &PrizeTierOf<T>, tier_share: BalanceOf<T>, winners: &[(BetOf<T>, u8)]) -> TierResultOf<T> {
+		let winning_stake = winners.iter()
+			.fold(BalanceOf::<T>::zero(), |acc, (bet, _)| acc.saturating_add(bet.bet));
+		let equal_share = tier_share / BalanceOf::<T>::from((winners.len() as u32).max(1));
+
+		TierResult {
+			min_hits: tier.min_hits,
+			winners: winners.len() as u32,
+			winning_stake,
+			share: tier_share,
+			fixed_prize: tier.fixed_prize.map(|fixed_prize| fixed_prize.min(equal_share)),
+		}
+	}
+
+	/// The tier of a ticket with `hits`, which is the one with the highest `min_hits` it reaches.
+	/// The hits and reward of every ticket of an account that won in a finalised session.
+	fn winning_tickets(game_id: GameId, session_id: SessionIdType, account_id: &T::AccountId) -> Vec<(u8, BalanceOf<T>)> {
+		Self::sessions(game_id, session_id)
+			.filter(|session_info| session_info.status == SessionStatus::Finalised)
+			.and_then(|session_info| {
+				let winners = Self::get_winners(&session_info.session_numbers?, Self::account_tickets(game_id, session_id, account_id));
+
+				Some(winners.into_iter()
+					.filter_map(|(bet, hits)| {
+						Self::ticket_tier(&session_info.tiers, hits).map(|tier| (hits, Self::ticket_reward(tier, bet.bet)))
+					})
+					.filter(|(_, ticket_reward)| !ticket_reward.is_zero())
+					.collect())
+			})
+			.unwrap_or_default()
+	}
+
+	fn ticket_tier(tiers: &[TierResultOf<T>], hits: u8) -> Option<&TierResultOf<T>> {
+		tiers.iter().rev().find(|tier| hits >= tier.min_hits)
+	}
+
+	fn ticket_reward(tier: &TierResultOf<T>, stake: BalanceOf<T>) -> BalanceOf<T> {
+		match tier.fixed_prize {
+			Some(fixed_prize) => fixed_prize,
+			None => Perbill::from_rational_approximation(stake, tier.winning_stake) * tier.share,
+		}
+	}
+
+	/// Whether `ClaimPeriod` sessions have closed after the session, so its rewards can no longer be claimed.
+	fn claim_period_over(game_id: GameId, session_id: SessionIdType) -> bool {
+		session_id.saturating_add(T::ClaimPeriod::get()) < Self::session_id(game_id)
+	}
+
+	fn finalize_session_with_numbers(game_id: GameId, session_id: SessionIdType, session_numbers: GuessNumbersOf<T>) -> DispatchResult {
+		let game = Self::game(game_id)?;
+
+		PendingSessions::<T>::mutate(game_id, |pending_sessions| pending_sessions.retain(|pending| *pending != session_id));
+
+		let session_bets = Self::session_bets(game_id, session_id);
+		let winners = Self::get_winners(&session_numbers, session_bets);
+
+		Self::deposit_event(Event::SessionResults(game_id, session_id, session_numbers.clone(), winners.clone()));
+
+		debug::info!("--- Finalize_the_session: {} of game {}", session_id, game_id);
+		debug::info!("--- Session_numbers: {:?}", session_numbers);
+		debug::info!("--- Winners: {:?}", winners);
+
+		let pot = SessionPot::<T>::take(game_id, session_id);
+		let rollover = Rollover::<T>::take(game_id);
+
+		let fees = Self::operator_fee() * pot;
+		let fees_paid = Self::distribute_fees(fees);
+		let pot_for_rewards = pot.saturating_sub(fees);
+
+		debug::info!("--- Session pot: {:?} $", pot);
+		debug::info!("--- Pot for fees: {:?} $", fees);
+		debug::info!("--- Pot for rewards: {:?} $", pot_for_rewards);
+		debug::info!("--- Rollover from previous sessions: {:?} $", rollover);
+
+		let mut rewards = BalanceOf::<T>::zero();
+		let mut allocated = BalanceOf::<T>::zero();
+		let mut tiers = Vec::new();
+
+		let prize_table = &game.prize_table;
+		let jackpot_tier = prize_table.len().checked_sub(1);
+
+		for (index, tier) in prize_table.iter().enumerate() {
+			let next_min_hits = prize_table.get(index + 1).map(|next_tier| next_tier.min_hits);
+			let mut tier_share = tier.percentage * pot_for_rewards;
+			allocated = allocated.saturating_add(tier_share);
+
+			if Some(index) == jackpot_tier {
+				tier_share = tier_share.saturating_add(rollover);
+			}
+
+			let tier_winners: WinnersOf<T> = winners.iter()
+				.filter(|(_, hits)| *hits >= tier.min_hits && next_min_hits.map_or(true, |next| *hits < next))
+				.cloned()
+				.collect();
 
-pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"whub");
+			let tier_result = Self::tier_result(tier, tier_share, &tier_winners);
+			let tier_rewards = tier_winners.iter()
+				.fold(BalanceOf::<T>::zero(), |acc, (bet, _)| acc.saturating_add(Self::ticket_reward(&tier_result, bet.bet)));
+			rewards = rewards.saturating_add(tier_rewards);
 
-pub mod crypto {
-	use super::KEY_TYPE;
-	use sp_runtime::{app_crypto::{app_crypto, sr25519}, MultiSigner, MultiSignature};
+			let tier_unpaid = tier_share.saturating_sub(tier_rewards);
+			if !tier_unpaid.is_zero() {
+				Self::deposit_event(Event::TierRolledOver(game_id, session_id, tier.min_hits, tier_unpaid));
+			}
 
-	app_crypto!(sr25519, KEY_TYPE);
+			tiers.push(tier_result);
+		}
 
-	pub struct TestAuthId;
+		// A session finalised after its claim period can never be claimed from.
+		if Self::claim_period_over(game_id, session_id) && !rewards.is_zero() {
+			Self::deposit_event(Event::RewardsExpired(game_id, session_id, rewards));
+			rewards = Zero::zero();
+		}
 
-	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TestAuthId {
-		type RuntimeAppPublic = Public;
-		type GenericSignature = sp_core::sr25519::Signature;
-		type GenericPublic = sp_core::sr25519::Public;
-	}
-}
+		let unallocated = pot_for_rewards.saturating_sub(allocated);
+		if !unallocated.is_zero() {
+			Self::deposit_event(Event::UnallocatedRolledOver(game_id, session_id, unallocated));
+		}
 
-pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> + orml_nft::Config<TokenData = NFTHash, ClassData = ()> {
-	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-	type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
-	type Call: From<Call<Self>>;
-	type Currency: Currency<Self::AccountId>;
-}
-type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+		// Fees that could not be paid out stay in the pallet account, so they roll over as well.
+		let next_rollover = pot_for_rewards
+			.saturating_add(fees.saturating_sub(fees_paid))
+			.saturating_add(rollover)
+			.saturating_sub(rewards);
+		Rollover::<T>::insert(game_id, next_rollover);
+		Self::deposit_event(Event::RolledOver(game_id, session_id, next_rollover));
+
+		Sessions::<T>::mutate(game_id, session_id, |session_info| {
+			if let Some(session_info) = session_info {
+				session_info.status = SessionStatus::Finalised;
+				session_info.session_numbers = Some(session_numbers);
+				session_info.total_pot = pot;
+				session_info.fee = fees;
+				session_info.tiers = tiers;
+				session_info.rewards = rewards;
+			}
+		});
 
-const BET_PRICE: u32 = 1000000000; // TODO: u128
-const SESSION_IN_BLOCKS: u32 = 5;
-const MIN_GUESS_NUMBER: u32 = 1;
-const MAX_GUESS_NUMBER: u32 = 10;
-const GUESS_NUMBERS_COUNT: usize = 6;
-const UNSIGNED_TX_PRIORITY: u64 = 100;
-const PALLET_ID: ModuleId = ModuleId(*b"JackPot!");
+		let (_, pot) = Self::pot();
+		debug::info!("--- Pot after: {:?} $", pot);
 
-type SessionIdType = u128;
-type GuessNumbersType = [u8; GUESS_NUMBERS_COUNT];
-type Winners<AccountId> = Vec<(Bet<AccountId>, u8)>;
-pub type NFTHash = Vec<u8>;
+		Ok(())
+	}
 
+	fn close_the_session(game_id: GameId) -> DispatchResult {
+		let game = Self::game(game_id)?;
+		let session_id = Self::next_session_id(game_id)?;
+		let block_number = <frame_system::Module<T>>::block_number();
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, PartialOrd, Ord)]
-pub struct NFTRequestData<AccountId, Balance> {
-	winner_account: AccountId,
-	reward: Balance,
-	score: u8,
-	score_out_of: u8,
-	session_id: SessionIdType,
-}
+		if let Some(expired_session_id) = session_id.checked_sub(T::ClaimPeriod::get()) {
+			Self::expire_unclaimed_rewards(game_id, expired_session_id);
+		}
+		if let Some(retired_session_id) = session_id.checked_sub(T::RetentionPeriod::get()) {
+			MissedReveals::<T>::remove(game_id, retired_session_id);
+		}
 
-type NFTRequestDataOf<T> = NFTRequestData<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+		// Sessions opened before `Sessions` existed have no record yet.
+		Sessions::<T>::mutate(game_id, session_id, |session_info| {
+			let session_info = session_info.get_or_insert_with(|| SessionInfo::open(Zero::zero()));
+			session_info.status = SessionStatus::Closed;
+			session_info.close_block = Some(block_number);
+			session_info.total_pot = Self::session_pot(game_id, session_id);
+		});
+		Sessions::<T>::insert(game_id, Self::session_id(game_id), SessionInfo::open(block_number.saturating_add(One::one())));
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct Bet<AccountId> {
-	account_id: AccountId,
-	guess_numbers: GuessNumbersType,
-}
+		PendingSessions::<T>::append(game_id, session_id);
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct SessionNumbersPayload<Public, BlockNumber> {
-	public: Public,
-	block_number: BlockNumber,
-	session_id: SessionIdType,
-	session_numbers: GuessNumbersType,
-}
+		Self::schedule_next_close(game_id, &game, block_number);
 
-impl<T: SigningTypes> SignedPayload<T> for SessionNumbersPayload<T::Public, T::BlockNumber> {
-	fn public(&self) -> T::Public {
-		self.public.clone()
+		match Self::draw_mode() {
+			DrawMode::Offchain => {
+				RevealDeadline::<T>::insert(game_id, session_id, block_number.saturating_add(T::RevealPeriod::get()));
+			},
+			DrawMode::OnChain => {
+				OnChainDrawAt::<T>::insert(game_id, session_id, block_number.saturating_add(T::OnChainDrawDelay::get()));
+			},
+		}
+
+		Ok(())
 	}
-}
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct NftHashPayload<Public> {
-	public: Public,
-	nft_hash: NFTHash,
-}
+	fn session_due(game_id: GameId, game: &GameOf<T>, block_number: T::BlockNumber) -> bool {
+		match game.schedule {
+			SessionSchedule::Blocks => block_number >= Self::session_close_block(game_id),
+			SessionSchedule::WallClock { period, offset } => {
+				// A game created at genesis has no close time until the first block.
+				let close_time = NextCloseTime::<T>::get(game_id).unwrap_or_else(|| {
+					let close_time = Self::next_wall_clock_close(period, offset);
+					NextCloseTime::<T>::insert(game_id, close_time);
+					close_time
+				});
 
-impl<T: SigningTypes> SignedPayload<T> for NftHashPayload<T::Public> {
-	fn public(&self) -> T::Public {
-		self.public.clone()
+				T::UnixTime::now().as_secs() >= close_time
+			},
+		}
 	}
-}
 
-decl_storage! {
-	trait Store for Module<T: Config> as WeHub {
-		ClassId get(fn class_id): T::ClassId;
-		SessionId get(fn session_id): SessionIdType;
-		SessionLength: T::BlockNumber = T::BlockNumber::from(SESSION_IN_BLOCKS);
-		Bets get(fn bets): map hasher(blake2_128_concat) SessionIdType => Vec<Bet<T::AccountId>>;
-		ClosedNotFinalisedSessionId get(fn closed_not_finalised_session): Option<SessionIdType>;
-		PendingWinnersNFT get(fn pending_winners_nft): Vec<NFTRequestDataOf<T>>;
-		Authorities get(fn authorities) config(offchain_authorities): Vec<T::AccountId>;
-	}
+	/// Sets when the session just opened closes. A changed session length applies from here on.
+	fn schedule_next_close(game_id: GameId, game: &GameOf<T>, block_number: T::BlockNumber) {
+		SessionCloseBlock::<T>::insert(game_id, block_number.saturating_add(game.session_length));
 
-	add_extra_genesis {
-		build(|_config| {
-			let class_id = NftModule::<T>::create_class(&Default::default(), Vec::new(), ()).expect("Cannot fail or invalid chain spec");
-			ClassId::<T>::put(class_id);
-		})
+		if let SessionSchedule::WallClock { period, offset } = game.schedule {
+			NextCloseTime::<T>::insert(game_id, Self::next_wall_clock_close(period, offset));
+		}
 	}
-}
 
-decl_event!(
-	pub enum Event<T> where
-		AccountId = <T as frame_system::Config>::AccountId,
-		Balance = BalanceOf<T>,
-		{
-		NewBet(SessionIdType, Bet<AccountId>),
-		Winners(SessionIdType, Winners<AccountId>),
-		SessionResults(SessionIdType, GuessNumbersType, Winners<AccountId>),
-		RewardFeeForAuthority(AccountId, Balance),
-		RewardForWinner(AccountId, Balance),
-	}
-);
-
-decl_error! {
-	pub enum Error for Module<T: Config> {
-		SessionIdOverflow,
-		TryToFinalizeTheSessionWhichIsNotClosed,
-		PendingWinnerDoesNotExist,
-		NftHttpFetchingError,
+	/// The first `offset + k * period` seconds since the unix epoch that is later than now.
+	fn next_wall_clock_close(period: u64, offset: u64) -> u64 {
+		let now = T::UnixTime::now().as_secs();
+		let elapsed = now.saturating_sub(offset) % period;
+
+		if now < offset {
+			offset
+		} else {
+			now.saturating_sub(elapsed).saturating_add(period)
+		}
 	}
-}
 
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		type Error = Error<T>;
+	/// Expires the oldest pending session of the game once its finalisation deadline has passed.
+	fn expire_overdue_session(game_id: GameId, block_number: T::BlockNumber) -> bool {
+		let session_id = match Self::closed_not_finalised_session(game_id) {
+			Some(session_id) => session_id,
+			None => return false,
+		};
 
-		fn deposit_event() = default;
+		let deadline_passed = Self::sessions(game_id, session_id)
+			.and_then(|session_info| session_info.close_block)
+			.map_or(false, |close_block| block_number > close_block.saturating_add(T::FinalisationDeadline::get()));
 
-		fn on_finalize(block_number: T::BlockNumber) {
-			if block_number % SessionLength::<T>::get() == T::BlockNumber::from(0u32) {
-				let _ = Self::close_the_session();
-			}
+		if !deadline_passed {
+			return false;
 		}
 
-		fn offchain_worker(block_number: T::BlockNumber) {
-			debug::RuntimeLogger::init();
+		Self::abandon_session(game_id, session_id, SessionStatus::Expired);
 
-			if let Some(session_id) = Self::closed_not_finalised_session() {
-				debug::info!("--- offchain_worker start block_number: {:?}, session_id: {}", block_number, session_id);
+		debug::info!("--- Session expired: {} of game {}", session_id, game_id);
+		Self::deposit_event(Event::SessionExpired(game_id, session_id));
 
-				if let Err(error) = Self::generate_session_numbers_and_send(block_number, session_id) {
-					debug::info!("--- offchain_worker error: {}", error);
-				}
+		true
+	}
+
+	/// Drops a pending session without drawing it and queues its bets for refunds.
+	fn abandon_session(game_id: GameId, session_id: SessionIdType, status: SessionStatus) {
+		PendingSessions::<T>::mutate(game_id, |pending_sessions| pending_sessions.retain(|pending| *pending != session_id));
+		Commitments::<T>::remove_prefix((game_id, session_id));
+		Reveals::<T>::remove(game_id, session_id);
+		RevealDeadline::<T>::remove(game_id, session_id);
+		OnChainDrawAt::<T>::remove(game_id, session_id);
+		SessionNumbersSubmissions::<T>::remove(game_id, session_id);
+		ConflictedSessions::<T>::remove(game_id, session_id);
+
+		Sessions::<T>::mutate(game_id, session_id, |session_info| {
+			if let Some(session_info) = session_info {
+				session_info.status = status;
 			}
+		});
+		ExpiredSessions::<T>::append((game_id, session_id));
+	}
 
-			const LOCK_DURATION_IN_BLOCKS: u32 = 3;
-			let mut lock_nft = StorageLock::<BlockAndTime<Self>>::with_block_deadline(b"wehub_nft::lock", LOCK_DURATION_IN_BLOCKS);
-			
-			let pending_winners_nft = Self::pending_winners_nft();
-			if pending_winners_nft.len() > 0 {
-				if let Ok(_guard) = lock_nft.try_lock() {
-					Self::generafte_pending_winners_nft(pending_winners_nft);
+	/// Refunds up to `max_refunds` bets of an expired session and returns the number of bets processed.
+	/// Once every bet is processed, whatever could not be refunded goes to the game's rollover.
+	fn refund_bets(game_id: GameId, session_id: SessionIdType, max_refunds: u32) -> u32 {
+		let bet_count = Self::bet_count(game_id, session_id);
+		let start = Self::refund_cursor(game_id, session_id);
+		let end = start.saturating_add(max_refunds).min(bet_count);
+
+		for index in start..end {
+			if let Some(bet) = Self::bets((game_id, session_id), index) {
+				if T::Currency::transfer(&Self::account_id(), &bet.account_id, bet.bet, KeepAlive).is_ok() {
+					SessionPot::<T>::mutate(game_id, session_id, |pot| *pot = pot.saturating_sub(bet.bet));
+					Self::deposit_event(Event::BetRefunded(game_id, session_id, bet.account_id, bet.bet));
 				}
 			}
 		}
 
-		#[weight = 10_000]
-		pub fn add_new_bet(origin, guess_numbers: GuessNumbersType) {
-			let account_id = ensure_signed(origin)?;
-			let session_id = SessionId::get();
+		if end < bet_count {
+			RefundCursor::<T>::insert(game_id, session_id, end);
+		} else {
+			RefundCursor::<T>::remove(game_id, session_id);
+			ExpiredSessions::<T>::mutate(|expired_sessions| expired_sessions.retain(|expired| *expired != (game_id, session_id)));
 
-			let new_bet = Bet {
-				account_id: account_id.clone(),
-				guess_numbers,
-			};
+			let not_refunded = SessionPot::<T>::take(game_id, session_id);
+			Rollover::<T>::mutate(game_id, |rollover| *rollover = rollover.saturating_add(not_refunded));
+
+			Self::deposit_event(Event::SessionRefunded(game_id, session_id));
+		}
+
+		end.saturating_sub(start)
+	}
+
+	fn session_bets(game_id: GameId, session_id: SessionIdType) -> Vec<BetOf<T>> {
+		(0..Self::bet_count(game_id, session_id))
+			.filter_map(|index| Self::bets((game_id, session_id), index))
+			.collect()
+	}
 
-			let bet_price: BalanceOf<T> = BET_PRICE.into(); // TODO: impl _u128.into()
+	/// Finalised sessions, and expired or cancelled ones whose refunds are done, can be pruned once they are older than
+	/// `RetentionPeriod` sessions of their game.
+	fn can_prune(game_id: GameId, session_id: SessionIdType) -> bool {
+		let old_enough = session_id.saturating_add(T::RetentionPeriod::get()) < Self::session_id(game_id);
+		let settled = match Self::sessions(game_id, session_id).map(|session_info| session_info.status) {
+			Some(SessionStatus::Finalised) => true,
+			Some(SessionStatus::Expired) | Some(SessionStatus::Cancelled) => !Self::expired_sessions().contains(&(game_id, session_id)),
+			_ => false,
+		};
+
+		old_enough && settled && Self::claim_period_over(game_id, session_id) && Self::bet_count(game_id, session_id) > 0
+	}
 
-			Bets::<T>::try_mutate(session_id, |bets| -> DispatchResult {
-				T::Currency::transfer(&account_id, &Self::account_id(), bet_price, KeepAlive)?;
-				bets.push(new_bet.clone());
-				Ok(())
-			})?;
+	fn prune_session_bets(game_id: GameId, session_id: SessionIdType) {
+		let bet_count = BetCount::<T>::take(game_id, session_id);
+		Bets::<T>::remove_prefix((game_id, session_id));
+		AccountTickets::<T>::remove_prefix((game_id, session_id));
+		ClaimedRewards::<T>::remove_prefix((game_id, session_id));
 
-			Self::deposit_event(RawEvent::NewBet(session_id, new_bet));
+		for (account_id, deposit) in BetDeposits::<T>::drain_prefix((game_id, session_id)) {
+			T::Currency::unreserve(&account_id, deposit);
 		}
 
-		#[weight = 10_000]
-		pub fn add_nft_hash_to_winner(origin, nft_request_data: NFTRequestDataOf<T>, payload: NftHashPayload<T::Public>, _singature: T::Signature) {
-			ensure_none(origin)?;
+		debug::info!("--- Pruned {} bets of session {} of game {}", bet_count, session_id, game_id);
+		Self::deposit_event(Event::SessionPruned(game_id, session_id, bet_count));
+	}
 
-			let mut pending_winners = Self::pending_winners_nft();
+	fn expire_unclaimed_rewards(game_id: GameId, session_id: SessionIdType) {
+		let expired = Self::sessions(game_id, session_id)
+			.map(|session_info| session_info.rewards.saturating_sub(session_info.claimed))
+			.unwrap_or_else(Zero::zero);
 
-			match pending_winners.binary_search(&nft_request_data) {
-				Ok(index) => {
-					let nft_token_id = NftModule::<T>::mint(&nft_request_data.winner_account, Self::class_id(), Vec::new(), payload.nft_hash.clone())?;
+		if !expired.is_zero() {
+			Rollover::<T>::mutate(game_id, |rollover| *rollover = rollover.saturating_add(expired));
+			Self::deposit_event(Event::RewardsExpired(game_id, session_id, expired));
+		}
+	}
 
-					pending_winners.remove(index);
-					PendingWinnersNFT::<T>::put(pending_winners);
+	fn draw_session_numbers_on_chain(game_id: GameId, session_id: SessionIdType) -> DispatchResult {
+		ensure!(
+			Self::closed_not_finalised_session(game_id) == Some(session_id),
+			Error::<T>::TryToFinalizeTheSessionWhichIsNotClosed
+		);
+		let game = Self::game(game_id)?;
 
-					let nft_hash_string = String::from_utf8(payload.nft_hash).expect("--- invalid utf-8");
-					debug::info!("--- add_nft_hash_to_winner: nft_token_id: {:?}, account_id: {:?} / nft_hash: {}", nft_token_id, nft_request_data.winner_account, nft_hash_string);
-				},
-				Err(_) => {
-					return Err(Error::<T>::PendingWinnerDoesNotExist.into())
-				},
-			};
+		let mut subject = b"wehub::draw::".to_vec();
+		subject.extend((game_id, session_id).encode());
+		let random = T::Randomness::random(&subject);
+		let seed = BlakeTwo256::hash_of(&(game_id, session_id, random));
+
+		Self::finalize_session_with_numbers(game_id, session_id, Self::draw_session_numbers(&game, seed))
+	}
+
+	/// The oldest closed session of the game that is not finalised yet. Sessions are finalised in this order.
+	fn closed_not_finalised_session(game_id: GameId) -> Option<SessionIdType> {
+		Self::pending_sessions(game_id).first().cloned()
+	}
+
+	fn has_revealed(game_id: GameId, session_id: SessionIdType, account_id: &T::AccountId) -> bool {
+		Self::reveals(game_id, session_id).iter().any(|(revealer, _)| revealer == account_id)
+	}
+
+	fn reveal_deadline_passed(game_id: GameId, session_id: SessionIdType) -> bool {
+		Self::reveal_deadline(game_id, session_id)
+			.map_or(false, |deadline| <frame_system::Module<T>>::block_number() > deadline)
+	}
+
+	/// The reveal phase is over once every committed authority has revealed or the deadline has passed.
+	fn reveal_phase_over(game_id: GameId, session_id: SessionIdType) -> bool {
+		let commitments_count = Commitments::<T>::iter_prefix((game_id, session_id)).count();
+		let all_revealed = commitments_count > 0 && Self::reveals(game_id, session_id).len() == commitments_count;
+
+		all_revealed || Self::reveal_deadline_passed(game_id, session_id)
+	}
+
+	/// Combines every revealed secret of the session into one seed and draws the session numbers from it.
+	/// The secrets are sorted first, so the result does not depend on the order the reveals were included in.
+	fn session_numbers_from_reveals(game_id: GameId, session_id: SessionIdType) -> Option<GuessNumbersOf<T>> {
+		let game = Self::games(game_id)?;
+		let mut secrets: Vec<SecretType> = Self::reveals(game_id, session_id).into_iter()
+			.map(|(_, secret)| secret)
+			.collect();
+
+		if secrets.is_empty() {
+			return None;
 		}
 
-		#[weight = 10_000]
-		pub fn finalize_the_session(origin, payload: SessionNumbersPayload<T::Public, T::BlockNumber>, _singature: T::Signature) {
-			ensure_none(origin)?;
+		secrets.sort();
+		let seed = BlakeTwo256::hash_of(&(game_id, session_id, secrets));
 
-			ClosedNotFinalisedSessionId::try_mutate(|x| -> DispatchResult {
-				match x {
-					None => return Err(Error::<T>::TryToFinalizeTheSessionWhichIsNotClosed)?,
-					Some(value) => {
-						if *value != payload.session_id {
-							return Err(Error::<T>::TryToFinalizeTheSessionWhichIsNotClosed)?
-						}
+		Some(Self::draw_session_numbers(&game, seed))
+	}
 
-						*x = None;
-						return Ok(())
-					},
-				};
-			})?;
-
-			let session_bets = Bets::<T>::get(payload.session_id);
-			let winners = Self::get_winners(payload.session_numbers, session_bets);
-
-			Self::deposit_event(RawEvent::SessionResults(payload.session_id, payload.session_numbers, winners.clone()));
-
-			debug::info!("--- Finalize_the_session: {}", payload.session_id);
-			debug::info!("--- Session_numbers: {:?}", payload.session_numbers);
-			debug::info!("--- Winners: {:?}", winners);
-
-			if winners.len() > 0 {
-				let (_, pot) = Self::pot();
-				let fees = Percent::from_percent(10) * pot;
-				let pot_for_rewards = pot.saturating_sub(fees);
-				let authorities = Self::authorities();
-				let authorities_count = authorities.len() as u32;
-				let reward_fee_per_authority: BalanceOf<T> = fees / authorities_count.into(); // TODO: fixed point safe division
-
-				debug::info!("--- Pot before: {:?}", pot);
-				debug::info!("--- Pot for fees: {:?} $", fees);
-				debug::info!("--- Pot for rewards: {:?} $", pot_for_rewards);
-
-				for authoritiy in authorities {
-					debug::info!("--- Reward for authority: {:?}, {:?} $", authoritiy, reward_fee_per_authority);
-					T::Currency::transfer(&Self::account_id(), &authoritiy, reward_fee_per_authority, KeepAlive)?;
-					Self::deposit_event(RawEvent::RewardFeeForAuthority(authoritiy, reward_fee_per_authority));
-				};
-
-				let winners_to_reward: Winners<T::AccountId> = winners.into_iter().filter(|&(_, hits) | hits >= 3).collect();
-				let winners_grouped_by_hits = winners_to_reward.group_by(|(_, a_hits), (_, b_hits)| a_hits == b_hits);
-
-				winners_grouped_by_hits.for_each(|winners| {
-					let hits = winners[0].1;
-
-					match hits {
-						3 => {
-							Self::distribute_reward(3, payload.session_id, winners, pot_for_rewards, hits);
-						},
-						4 => {
-							Self::distribute_reward(7, payload.session_id, winners, pot_for_rewards, hits);
-						},
-						5 => {
-							Self::distribute_reward(15, payload.session_id, winners, pot_for_rewards, hits);
-						},
-						6 => {
-							Self::distribute_reward(75, payload.session_id, winners, pot_for_rewards, hits);
-						},
-						_ => debug::info!("Error distribute_reward"), // TODO: handle Error
-					}
-				});
+	/// The weight of finalising a session, charging every ticket as a winner, which is the worst case.
+	fn finalisation_weight(game_id: GameId, session_id: SessionIdType) -> Weight {
+		let authorities_count = Authorities::<T>::decode_len().unwrap_or(0) as u32;
 
-				let (_, pot) = Self::pot();
-				debug::info!("--- Pot after: {:?} $", pot);
-			}
+		T::WeightInfo::finalize_the_session(0, Self::bet_count(game_id, session_id), authorities_count)
+	}
+
+	fn finalisation_quorum() -> u32 {
+		let authorities_count = Self::authorities().len() as u32;
+
+		T::FinalisationThreshold::get().min(authorities_count).max(1)
+	}
+
+	fn record_missed_reveals(game_id: GameId, session_id: SessionIdType) {
+		let missed_reveals: Vec<T::AccountId> = Self::authorities().into_iter()
+			.filter(|authority| !Self::has_revealed(game_id, session_id, authority))
+			.collect();
+
+		Commitments::<T>::remove_prefix((game_id, session_id));
+		Reveals::<T>::remove(game_id, session_id);
+		RevealDeadline::<T>::remove(game_id, session_id);
+
+		if !missed_reveals.is_empty() {
+			debug::info!("--- Missed reveals: {:?}", missed_reveals);
+			MissedReveals::<T>::insert(game_id, session_id, &missed_reveals);
+			Self::deposit_event(Event::MissedReveals(game_id, session_id, missed_reveals));
 		}
 	}
-}
 
-impl<T: Config> Module<T> {
-	pub fn account_id() -> T::AccountId {
-		PALLET_ID.into_account()
+	/// Draws `pick_count` distinct numbers in the game's range. A valid game never has more than `MaxPickCount`.
+	fn draw_session_numbers(game: &GameOf<T>, seed: <BlakeTwo256 as HashT>::Output) -> GuessNumbersOf<T> {
+		let mut rng = RandomNumberGenerator::<BlakeTwo256>::new(seed);
+		let mut session_numbers = GuessNumbersOf::<T>::default();
+
+		while session_numbers.len() < game.pick_count as usize {
+			let next_session_number = (rng.pick_u32((game.max_number - game.min_number) as u32) + game.min_number as u32) as u8;
+			if !session_numbers.contains(&next_session_number) && session_numbers.try_push(next_session_number).is_err() {
+				break;
+			}
+		}
+
+		session_numbers
 	}
 
-	fn pot() -> (T::AccountId, BalanceOf<T>) {
-			let account_id = Self::account_id();
-			let balance = T::Currency::free_balance(&account_id)
-				.saturating_sub(T::Currency::minimum_balance());
+	fn place_bets(game_id: GameId, account_id: &T::AccountId, new_bets: Vec<BetOf<T>>) -> DispatchResult {
+		ensure!(!Self::is_paused(PauseTarget::Betting), Error::<T>::BettingPaused);
 
-			(account_id, balance)
+		let session_id = Self::session_id(game_id);
+
+		let new_bets_count = new_bets.len() as u32;
+
+		let bet_count = Self::bet_count(game_id, session_id);
+		let next_bet_count = bet_count
+			.checked_add(new_bets_count)
+			.filter(|count| *count <= T::MaxTicketsPerSession::get())
+			.ok_or(Error::<T>::TooManyTicketsInSession)?;
+
+		let mut account_tickets = AccountTickets::<T>::get((game_id, session_id), account_id);
+		for index in bet_count..next_bet_count {
+			account_tickets.try_push(index).map_err(|_| Error::<T>::TooManyTicketsPerAccount)?;
+		}
+
+		let total_stake = new_bets.iter()
+			.fold(BalanceOf::<T>::zero(), |acc, bet| acc.saturating_add(bet.bet));
+		let deposit = T::BetDeposit::get().saturating_mul(BalanceOf::<T>::from(new_bets_count));
+
+		T::Currency::reserve(account_id, deposit)?;
+		if let Err(error) = T::Currency::transfer(account_id, &Self::account_id(), total_stake, KeepAlive) {
+			T::Currency::unreserve(account_id, deposit);
+			return Err(error);
+		}
+
+		AccountTickets::<T>::insert((game_id, session_id), account_id, account_tickets);
+		BetDeposits::<T>::mutate((game_id, session_id), account_id, |reserved| *reserved = reserved.saturating_add(deposit));
+		SessionPot::<T>::mutate(game_id, session_id, |pot| *pot = pot.saturating_add(total_stake));
+		BetCount::<T>::insert(game_id, session_id, next_bet_count);
+
+		for (index, new_bet) in (bet_count..next_bet_count).zip(new_bets) {
+			Bets::<T>::insert((game_id, session_id), index, &new_bet);
+			Self::deposit_event(Event::NewBet(game_id, session_id, new_bet));
+		}
+
+		Ok(())
 	}
 
-	fn distribute_reward(reward_percentage: u8, session_id: SessionIdType, winners: &[(Bet<T::AccountId>, u8)], pot_for_rewards: BalanceOf<T>, hits: u8) {
-		let rewards_from_pot = Percent::from_percent(reward_percentage) * pot_for_rewards;
-		let winners_count = winners.len() as u32;
-		let reward_per_winner: BalanceOf<T> = rewards_from_pot / winners_count.into(); // TODO: fixed point safe division
+	fn ensure_valid_game(game: &GameOf<T>) -> DispatchResult {
+		ensure!(
+			game.pick_count > 0 &&
+				game.pick_count as u32 <= T::MaxPickCount::get() &&
+				game.min_number <= game.max_number &&
+				(game.max_number - game.min_number) as u32 + 1 >= game.pick_count as u32,
+			Error::<T>::InvalidGameNumbers
+		);
+		ensure!(!game.ticket_price.is_zero(), Error::<T>::ZeroTicketPrice);
+		ensure!(!game.session_length.is_zero(), Error::<T>::InvalidSessionLength);
+		Self::ensure_valid_schedule(&game.schedule)?;
+
+		Self::ensure_valid_prize_table(&game.prize_table, game.pick_count)
+	}
 
-		winners.iter().for_each(|winner| {
-			let winner_account = &winner.0.account_id;
-			debug::info!("Account {:?} won {:?} $ by guessing {:?} numbers!", winner_account, reward_per_winner, hits);
-			let _ = T::Currency::transfer(&Self::account_id(), winner_account, reward_per_winner, KeepAlive); // TODO: handle erorr
+	fn ensure_valid_schedule(session_schedule: &SessionSchedule) -> DispatchResult {
+		if let SessionSchedule::WallClock { period, offset } = *session_schedule {
+			ensure!(period > 0 && offset < period, Error::<T>::InvalidSessionSchedule);
+		}
 
-			PendingWinnersNFT::<T>::mutate(|pending_winners_nft| {
-				let nft_request_data = NFTRequestData {
-					winner_account: winner_account.clone(),
-					reward: reward_per_winner,
-					score: hits,
-					score_out_of: GUESS_NUMBERS_COUNT as u8,
-					session_id,
-				};
-
-				pending_winners_nft.push(nft_request_data);
-			});
+		Ok(())
+	}
 
-			Self::deposit_event(RawEvent::RewardForWinner(winner_account.clone(), reward_per_winner));
-		})
+	/// The tiers have to be sorted by strictly increasing `min_hits` and share at most 100% of the pot.
+	fn ensure_valid_prize_table(prize_table: &[PrizeTierOf<T>], pick_count: u8) -> DispatchResult {
+		let total_percentage = prize_table.iter()
+			.fold(0u32, |acc, tier| acc + tier.percentage.deconstruct() as u32);
+		ensure!(total_percentage <= 100, Error::<T>::PrizeTableExceedsPot);
+
+		let mut previous_min_hits = 0;
+		for tier in prize_table.iter() {
+			ensure!(
+				tier.min_hits > previous_min_hits && tier.min_hits <= pick_count,
+				Error::<T>::InvalidPrizeTierHits
+			);
+			previous_min_hits = tier.min_hits;
+		}
+
+		Ok(())
 	}
 
-	fn close_the_session() -> DispatchResult {
-		let session_id = Self::next_session_id()?;
-		ClosedNotFinalisedSessionId::put(session_id);
+	fn ensure_valid_guess_numbers(game: &GameOf<T>, guess_numbers: &[u8]) -> DispatchResult {
+		ensure!(guess_numbers.len() == game.pick_count as usize, Error::<T>::WrongNumberOfGuessNumbers);
+
+		let mut seen = BTreeSet::new();
+
+		for &number in guess_numbers.iter() {
+			ensure!(
+				(game.min_number..=game.max_number).contains(&number),
+				Error::<T>::GuessNumberOutOfRange
+			);
+			ensure!(seen.insert(number), Error::<T>::DuplicateGuessNumber);
+		}
+
 		Ok(())
 	}
 
-	fn get_winners(session_numbers: GuessNumbersType, session_bets: Vec<Bet<T::AccountId>>) -> Winners<T::AccountId> {
+	fn get_winners(session_numbers: &[u8], session_bets: Vec<BetOf<T>>) -> WinnersOf<T> {
+		let session_numbers: BTreeSet<u8> = session_numbers.iter().cloned().collect();
+
 		session_bets.into_iter()
 			.map(|bet| {
-				let correct = session_numbers.iter()
-					.filter(|n| bet.guess_numbers.contains(n))
-					.fold(0, |acc, _| acc + 1);
+				let guess_numbers: BTreeSet<u8> = bet.guess_numbers.iter().cloned().collect();
+				let correct = session_numbers.intersection(&guess_numbers).count() as u8;
 
 				(bet, correct)
 			})
 			.filter(|x| x.1 > 0)
-			.collect::<Winners<T::AccountId>>()
+			.collect::<WinnersOf<T>>()
 	}
 
-	fn next_session_id() -> Result<SessionIdType, DispatchError> {
-		let session_id = SessionId::get();
+	fn next_session_id(game_id: GameId) -> Result<SessionIdType, DispatchError> {
+		let session_id = Self::session_id(game_id);
 		let next_session_id = session_id.checked_add(1).ok_or(Error::<T>::SessionIdOverflow)?;
-		SessionId::put(next_session_id);
+		SessionId::<T>::insert(game_id, next_session_id);
 
 		Ok(session_id)
 	}
@@ -423,24 +2034,101 @@ impl<T: Config> Module<T> {
 		Self::authorities().contains(account_id)
 	}
 
+	// TODO - use aura keys to check authority
+	fn authority_account_from_payload<P: SignedPayload<T>>(payload: &P, signature: &T::Signature) -> Result<T::AccountId, InvalidTransaction> {
+		let account_id = payload.public().into_account();
+		if !Self::is_authority_account(&account_id) {
+			return Err(InvalidTransaction::BadProof);
+		}
+
+		if !payload.verify::<T::AuthorityId>(signature.clone()) {
+			return Err(InvalidTransaction::BadProof);
+		}
+
+		Ok(account_id)
+	}
+
 	#[cfg(test)]
-	fn set_session_id(session_id: SessionIdType) {
-		SessionId::put(session_id);
+	fn set_session_id(game_id: GameId, session_id: SessionIdType) {
+		SessionId::<T>::insert(game_id, session_id);
 	}
 
 
 
 	// --- Off-chain workers ------------------------
 
-	fn generate_session_numbers_and_send(block_number: T::BlockNumber, session_id: SessionIdType) -> Result<(), &'static str> {
-		let session_numbers = Self::get_session_numbers();
+	fn secret_storage_key(game_id: GameId, session_id: SessionIdType) -> Vec<u8> {
+		let mut key = b"wehub::session_secret::".to_vec();
+		key.extend((game_id, session_id).encode());
+		key
+	}
+
+	fn commit_session_secret_and_send(game_id: GameId, session_id: SessionIdType) -> Result<(), &'static str> {
+		let storage_key = Self::secret_storage_key(game_id, session_id);
+		let storage = StorageValueRef::persistent(&storage_key);
+
+		let secret = match storage.get::<SecretType>() {
+			Some(Some(secret)) => secret,
+			_ => {
+				let secret: SecretType = offchain::random_seed();
+				storage.set(&secret);
+				secret
+			},
+		};
+		let commitment = T::Hashing::hash_of(&secret);
+
+		let (_account, result) = Signer::<T, T::AuthorityId>::any_account().send_unsigned_transaction(
+			|account| CommitPayload {
+				public: account.public.clone(),
+				game_id,
+				session_id,
+				commitment,
+			},
+			|payload, signature| {
+				Call::commit_session_secret(payload, signature)
+			}
+		).ok_or("No local accounts accounts available")?;
+
+		result.map_err(|()| "Unable to submit transaction")?;
+
+		Ok(())
+	}
+
+	fn reveal_session_secret_and_send(game_id: GameId, session_id: SessionIdType) -> Result<(), &'static str> {
+		let storage_key = Self::secret_storage_key(game_id, session_id);
+		let storage = StorageValueRef::persistent(&storage_key);
+		let secret = match storage.get::<SecretType>() {
+			Some(Some(secret)) => secret,
+			_ => return Err("No secret committed for the session"),
+		};
+
+		let (_account, result) = Signer::<T, T::AuthorityId>::any_account().send_unsigned_transaction(
+			|account| RevealPayload {
+				public: account.public.clone(),
+				game_id,
+				session_id,
+				secret,
+			},
+			|payload, signature| {
+				Call::reveal_session_secret(payload, signature)
+			}
+		).ok_or("No local accounts accounts available")?;
+
+		result.map_err(|()| "Unable to submit transaction")?;
+
+		Ok(())
+	}
+
+	fn generate_session_numbers_and_send(block_number: T::BlockNumber, game_id: GameId, session_id: SessionIdType) -> Result<(), &'static str> {
+		let session_numbers = Self::session_numbers_from_reveals(game_id, session_id).ok_or("No reveals for the session")?;
 
 		let (_account, result) = Signer::<T, T::AuthorityId>::any_account().send_unsigned_transaction(
 			|account| SessionNumbersPayload {
 				public: account.public.clone(),
 				block_number,
+				game_id,
 				session_id,
-				session_numbers,
+				session_numbers: session_numbers.clone(),
 			},
 			|payload, signature| {
 				Call::finalize_the_session(payload, signature)
@@ -479,15 +2167,17 @@ impl<T: Config> Module<T> {
 		let reward = TryInto::<u128>::try_into(nft_request_data.reward).unwrap_or(0);
 
 		let request_body = "{
-			\"score\": {SCORE}, 
+			\"score\": {SCORE},
 			\"scoreOutOf\": {SCORE_OUT_OF},
 			\"reward\": {REWARD},
+			\"gameId\": {GAME_ID},
 			\"sessionId\": {SESSION_ID}
 		}"
 			.to_string()
 			.replace("{SCORE}", &nft_request_data.score.to_string())
 			.replace("{SCORE_OUT_OF}", &nft_request_data.score_out_of.to_string())
 			.replace("{REWARD}", &reward.to_string())
+			.replace("{GAME_ID}", &nft_request_data.game_id.to_string())
 			.replace("{SESSION_ID}", &nft_request_data.session_id.to_string());
 
 		let mut request_vector = Vec::new();
@@ -534,88 +2224,9 @@ impl<T: Config> Module<T> {
 
 		Ok(())
 	}
-
-	fn get_random_number() -> u8 {
-		let random_seed = offchain::random_seed();
-		let mut rng = RandomNumberGenerator::<BlakeTwo256>::new(random_seed.into());
-
-		(rng.pick_u32(MAX_GUESS_NUMBER - MIN_GUESS_NUMBER) + MIN_GUESS_NUMBER) as u8
-	}
-
-	fn get_session_numbers() -> GuessNumbersType {
-		let mut session_numbers: GuessNumbersType = [0; GUESS_NUMBERS_COUNT];
-
-		let mut i = 0;
-		loop {
-			let next_session_number = Self::get_random_number();
-			if !session_numbers.contains(&next_session_number) {
-				session_numbers[i] = next_session_number;
-				i += 1;
-			}
-
-			if i == GUESS_NUMBERS_COUNT {
-				break;
-			}
-		}
-
-		session_numbers
-	}
-}
-
-impl<T: Config> ValidateUnsigned for Module<T> {
-	type Call = Call<T>;
-
-	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-		match call {
-			Call::finalize_the_session(ref payload, ref signature) => {
-				// TODO - use aura keys to check authority
-
-				let account_id = payload.public.clone().into_account();
-				if !Self::is_authority_account(&account_id) {
-					return InvalidTransaction::BadProof.into();
-				}
-				
-				let valid_signature = SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
-				if !valid_signature {
-					return InvalidTransaction::BadProof.into();
-				}
-
-				return ValidTransaction::with_tag_prefix("WeHub/validate_unsigned/finalize_the_session")
-					.priority(UNSIGNED_TX_PRIORITY)
-					.and_provides(account_id)
-					.and_provides(payload.session_id)
-					.longevity(5)
-					.propagate(true)
-					.build();
-			},
-			Call::add_nft_hash_to_winner(_ntf_request_data, ref payload, ref signature) => {
-				// TODO - Refactor this method to be used in finalize_the_session & add_nft_hash_to_winner
-				// TODO - use aura keys to check authority
-
-				let account_id = payload.public.clone().into_account();
-				if !Self::is_authority_account(&account_id) {
-					return InvalidTransaction::BadProof.into();
-				}
-
-				let valid_signature = SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
-				if !valid_signature {
-					return InvalidTransaction::BadProof.into();
-				}
-
-				return ValidTransaction::with_tag_prefix("WeHub/validate_unsigned/add_nft_hash_to_winner")
-					.priority(UNSIGNED_TX_PRIORITY)
-					.and_provides(account_id)
-					.and_provides(&payload.nft_hash)
-					.longevity(5)
-					.propagate(true)
-					.build();
-			}
-			_ => return InvalidTransaction::Call.into(),
-		};
-	}
 }
 
-impl<T: Config> rt_offchain::storage_lock::BlockNumberProvider for Module<T> {
+impl<T: Config> rt_offchain::storage_lock::BlockNumberProvider for Pallet<T> {
 	type BlockNumber = T::BlockNumber;
 	fn current_block_number() -> Self::BlockNumber {
 		<frame_system::Module<T>>::block_number()
@@ -0,0 +1,264 @@
+//! Storage migrations, run from `on_runtime_upgrade` until `StorageVersion` reaches the latest `Releases`.
+
+use super::*;
+
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+
+	if StorageVersion::<T>::get() == Releases::V1_0_0 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+	}
+
+	weight
+}
+
+/// Checks run around `migrate`. Substrate 3.0 has neither the `pre_upgrade` and `post_upgrade` hooks nor
+/// the `try-runtime` node subcommand to call them from, which arrive in a later Substrate release. Until the
+/// node is upgraded to it, they are run by the migration tests and by anything built with the `try-runtime`
+/// feature against a copy of the chain state.
+#[cfg(any(test, feature = "try-runtime"))]
+pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+	if StorageVersion::<T>::get() == Releases::V1_0_0 {
+		v2::pre_migrate::<T>()?;
+	}
+
+	Ok(())
+}
+
+#[cfg(any(test, feature = "try-runtime"))]
+pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+	ensure!(StorageVersion::<T>::get() == Releases::V2_0_0, "StorageVersion was not updated");
+
+	v2::post_migrate::<T>()
+}
+
+/// From the single 6 of 1..=10 game of `decl_storage` to games, bets stored per ticket and
+/// NFT requests that know their game. Everything from V1 becomes game 0.
+pub(crate) mod v2 {
+	use super::*;
+	use frame_support::{
+		Blake2_128Concat,
+		storage::migration::{storage_key_iter, take_storage_value},
+	};
+	use sp_std::convert::TryFrom;
+
+	const MODULE: &[u8] = b"WeHub";
+	const GAME_ID: GameId = 0;
+	/// The price every V1 ticket was bought for.
+	pub(crate) const BET_PRICE: u32 = 1_000_000_000;
+
+	/// `Bets: map SessionIdType => Vec<Bet<AccountId>>` held these, without a stake.
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldBet<AccountId> {
+		pub(crate) account_id: AccountId,
+		pub(crate) guess_numbers: [u8; 6],
+	}
+
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldNFTRequestData<AccountId, Balance> {
+		pub(crate) winner_account: AccountId,
+		pub(crate) reward: Balance,
+		pub(crate) score: u8,
+		pub(crate) score_out_of: u8,
+		pub(crate) session_id: SessionIdType,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads: Weight = 4;
+		let mut writes: Weight = 4;
+
+		let session_id = take_storage_value::<SessionIdType>(MODULE, b"SessionId", &[]).unwrap_or_default();
+		let session_length = take_storage_value::<T::BlockNumber>(MODULE, b"SessionLength", &[]);
+		let closed_session_id = take_storage_value::<SessionIdType>(MODULE, b"ClosedNotFinalisedSessionId", &[]);
+
+		// The old single-value `SessionId` lives at the prefix of the new map, so it is taken before game 0 is keyed.
+		SessionId::<T>::insert(GAME_ID, session_id);
+
+		if !Games::<T>::contains_key(GAME_ID) {
+			let mut game = classic_game::<T::BlockNumber, BalanceOf<T>>(BalanceOf::<T>::from(BET_PRICE));
+			if let Some(session_length) = session_length {
+				game.session_length = session_length;
+			}
+
+			if let Err(error) = Pallet::<T>::do_create_game(game) {
+				debug::info!("--- Migration could not create game {}: {:?}", GAME_ID, error);
+			}
+			writes += 4;
+		}
+
+		// Collected first: the new `Bets` shares the old prefix and must not be visited while it is drained.
+		let old_bets: Vec<(SessionIdType, Vec<OldBet<T::AccountId>>)> =
+			storage_key_iter::<SessionIdType, Vec<OldBet<T::AccountId>>, Blake2_128Concat>(MODULE, b"Bets")
+				.drain()
+				.collect();
+		let bet_price = BalanceOf::<T>::from(BET_PRICE);
+		let mut session_pots = BalanceOf::<T>::zero();
+
+		for (bet_session_id, bets) in old_bets {
+			let unsettled = bet_session_id == session_id || Some(bet_session_id) == closed_session_id;
+			let mut bet_count = 0u32;
+			reads += 1;
+			writes += 2;
+
+			for old_bet in bets {
+				let guess_numbers = match GuessNumbersOf::<T>::try_from(old_bet.guess_numbers.to_vec()) {
+					Ok(guess_numbers) => guess_numbers,
+					Err(()) => {
+						debug::info!("--- Migration dropped a bet of session {} exceeding MaxPickCount", bet_session_id);
+						continue;
+					},
+				};
+
+				// v1 did not limit the tickets of an account. Tickets beyond `MaxTicketsPerAccount` could never be
+				// claimed, so they are refunded while their session is unsettled. Settled ones were paid by v1.
+				let indexed = AccountTickets::<T>::mutate((GAME_ID, bet_session_id), &old_bet.account_id, |tickets| {
+					tickets.try_push(bet_count).is_ok()
+				});
+				writes += 1;
+
+				if !indexed && unsettled {
+					reads += 1;
+					writes += 2;
+					if T::Currency::transfer(&Pallet::<T>::account_id(), &old_bet.account_id, bet_price, KeepAlive).is_ok() {
+						Pallet::<T>::deposit_event(Event::BetRefunded(GAME_ID, bet_session_id, old_bet.account_id, bet_price));
+					} else {
+						debug::error!("--- Migration could not refund a ticket of session {} exceeding MaxTicketsPerAccount, it rolls over", bet_session_id);
+					}
+					continue;
+				}
+
+				if unsettled {
+					SessionPot::<T>::mutate(GAME_ID, bet_session_id, |pot| *pot = pot.saturating_add(bet_price));
+					session_pots = session_pots.saturating_add(bet_price);
+					writes += 1;
+				}
+
+				Bets::<T>::insert((GAME_ID, bet_session_id), bet_count, Bet {
+					account_id: old_bet.account_id,
+					guess_numbers,
+					bet: bet_price,
+				});
+				bet_count += 1;
+				writes += 1;
+			}
+
+			BetCount::<T>::insert(GAME_ID, bet_session_id, bet_count);
+
+			// v1 paid settled sessions out when it drew them, so they are recorded as finalised without numbers.
+			// Nothing can be claimed from them, and their bets are pruned like those of any finalised session.
+			if !unsettled && !Sessions::<T>::contains_key(GAME_ID, bet_session_id) {
+				let mut session_info = SessionInfoOf::<T>::open(Zero::zero());
+				session_info.status = SessionStatus::Finalised;
+				session_info.total_pot = bet_price.saturating_mul(BalanceOf::<T>::from(bet_count));
+
+				Sessions::<T>::insert(GAME_ID, bet_session_id, session_info);
+				reads += 1;
+				writes += 1;
+			}
+		}
+
+		// V1 drew a closed session's numbers without a commit-reveal round, so nothing was committed for it.
+		// It is drawn on chain or expires and is refunded, like any session closed by `close_the_session`.
+		if let Some(closed_session_id) = closed_session_id {
+			let block_number = <frame_system::Module<T>>::block_number();
+			let mut session_info = SessionInfoOf::<T>::open(Zero::zero());
+			session_info.status = SessionStatus::Closed;
+			session_info.close_block = Some(block_number);
+			session_info.total_pot = Pallet::<T>::session_pot(GAME_ID, closed_session_id);
+
+			Sessions::<T>::insert(GAME_ID, closed_session_id, session_info);
+			PendingSessions::<T>::insert(GAME_ID, vec![closed_session_id]);
+
+			match Pallet::<T>::draw_mode() {
+				DrawMode::Offchain => RevealDeadline::<T>::insert(GAME_ID, closed_session_id, block_number),
+				DrawMode::OnChain => OnChainDrawAt::<T>::insert(GAME_ID, closed_session_id, block_number.saturating_add(T::OnChainDrawDelay::get())),
+			}
+			writes += 3;
+		}
+
+		// Whatever the pallet holds beyond the open and closed sessions was the V1 jackpot.
+		let (_, pot) = Pallet::<T>::pot();
+		Rollover::<T>::insert(GAME_ID, pot.saturating_sub(session_pots));
+
+		let translated = PendingWinnersNFT::<T>::translate::<Vec<OldNFTRequestData<T::AccountId, BalanceOf<T>>>, _>(|pending_winners| {
+			pending_winners.map(|pending_winners| pending_winners.into_iter()
+				.map(|old| NFTRequestData {
+					winner_account: old.winner_account,
+					reward: old.reward,
+					score: old.score,
+					score_out_of: old.score_out_of,
+					game_id: GAME_ID,
+					session_id: old.session_id,
+				})
+				.collect())
+		});
+		if translated.is_err() {
+			debug::info!("--- Migration could not decode PendingWinnersNFT, it was cleared");
+			PendingWinnersNFT::<T>::kill();
+		}
+
+		StorageVersion::<T>::put(Releases::V2_0_0);
+
+		debug::info!("--- Migrated pallet-wehub storage to V2_0_0");
+
+		T::DbWeight::get().reads_writes(reads + 1, writes + 2)
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	const PRE_MIGRATE_KEY: &[u8] = b":wehub:migration:v2";
+
+	/// Counts the V1 bets that are kept and the NFT requests, and keeps the counts in the state for `post_migrate`.
+	#[cfg(any(test, feature = "try-runtime"))]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		use frame_support::storage::{migration::get_storage_value, unhashed};
+		use sp_std::collections::btree_map::BTreeMap;
+
+		let session_id = get_storage_value::<SessionIdType>(MODULE, b"SessionId", &[]).unwrap_or_default();
+		let closed_session_id = get_storage_value::<SessionIdType>(MODULE, b"ClosedNotFinalisedSessionId", &[]);
+
+		// The tickets `migrate` refunds are not kept.
+		let bet_count: u32 = storage_key_iter::<SessionIdType, Vec<OldBet<T::AccountId>>, Blake2_128Concat>(MODULE, b"Bets")
+			.map(|(bet_session_id, bets)| {
+				if bet_session_id != session_id && Some(bet_session_id) != closed_session_id {
+					return bets.len() as u32;
+				}
+
+				let mut tickets_per_account = BTreeMap::new();
+				for bet in bets {
+					*tickets_per_account.entry(bet.account_id).or_insert(0u32) += 1;
+				}
+				tickets_per_account.values().map(|tickets| (*tickets).min(T::MaxTicketsPerAccount::get())).sum()
+			})
+			.sum();
+		let pending_winners_count = get_storage_value::<Vec<OldNFTRequestData<T::AccountId, BalanceOf<T>>>>(MODULE, b"PendingWinnersNFT", &[])
+			.map_or(0, |pending_winners| pending_winners.len() as u32);
+
+		unhashed::put(PRE_MIGRATE_KEY, &(bet_count, pending_winners_count));
+
+		Ok(())
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		use frame_support::storage::{migration::get_storage_value, unhashed};
+
+		let (bet_count, pending_winners_count) = unhashed::take::<(u32, u32)>(PRE_MIGRATE_KEY)
+			.ok_or("pre_migrate did not run before the migration")?;
+
+		ensure!(Games::<T>::contains_key(GAME_ID), "Game 0 was not created");
+		ensure!(
+			BetCount::<T>::iter_prefix_values(GAME_ID).sum::<u32>() == bet_count,
+			"Bets were lost in the migration"
+		);
+		ensure!(
+			Pallet::<T>::pending_winners_nft().len() as u32 == pending_winners_count,
+			"NFT requests were lost in the migration"
+		);
+		ensure!(
+			get_storage_value::<SessionIdType>(MODULE, b"ClosedNotFinalisedSessionId", &[]).is_none(),
+			"ClosedNotFinalisedSessionId was not removed"
+		);
+
+		Ok(())
+	}
+}
@@ -4,12 +4,14 @@ use crate as pallet_wehub;
 use sp_core::H256;
 use frame_support::parameter_types;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, Extrinsic as ExtrinsicT},
+	testing::{Header, TestXt, TestSignature, UintAuthorityId},
 };
 use frame_system as system;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
+type Extrinsic = TestXt<Call, ()>;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -19,7 +21,9 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		WeHub: pallet_wehub::{Module, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		Nft: orml_nft::{Module, Storage},
+		WeHub: pallet_wehub::{Module, Call, Storage, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -46,13 +50,69 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = SS58Prefix;
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl orml_nft::Config for Test {
+	type ClassId = u32;
+	type TokenId = u64;
+	type ClassData = ();
+	type TokenData = NFTHash;
+}
+
+/// Signs with `UintAuthorityId`, whose account is its own `u64`.
+pub struct TestAuthId;
+
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+impl SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> system::offchain::SendTransactionTypes<LocalCall> for Test
+where Call: From<LocalCall>
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Test
+where Call: From<LocalCall>
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
 pub struct MockRandom;
 
 impl Randomness<H256> for MockRandom {
@@ -61,12 +121,75 @@ impl Randomness<H256> for MockRandom {
     }
 }
 
+thread_local! {
+	pub static NOW: std::cell::RefCell<u64> = std::cell::RefCell::new(0);
+}
+
+pub struct MockTime;
+
+impl MockTime {
+	pub fn set_timestamp(seconds: u64) {
+		NOW.with(|now| *now.borrow_mut() = seconds);
+	}
+}
+
+impl UnixTime for MockTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(NOW.with(|now| *now.borrow()))
+	}
+}
+
+parameter_types! {
+	pub const TicketPrice: u64 = 100;
+	pub const MaxGames: u32 = 4;
+	pub const MaxPickCount: u32 = 6;
+	pub const MaxBet: u64 = 10_000;
+	pub const MaxTicketsPerAccount: u32 = 3;
+	pub const RevealPeriod: u64 = 2;
+	pub const FinalisationThreshold: u32 = 2;
+	pub const OnChainDrawDelay: u64 = 1;
+	pub const ClaimPeriod: u128 = 2;
+	pub const FinalisationDeadline: u64 = 10;
+	pub const RefundBatchSize: u32 = 2;
+	pub const MaxTicketsPerSession: u32 = 5;
+	pub const RetentionPeriod: u128 = 3;
+	pub const BetDeposit: u64 = 1;
+}
+
 impl pallet_wehub::Config for Test {
 	type Event = Event;
+	type AuthorityId = TestAuthId;
+	type Call = Call;
+	type Currency = Balances;
 	type Randomness = MockRandom;
+	type UnixTime = MockTime;
+	type MaxGames = MaxGames;
+	type MaxPickCount = MaxPickCount;
+	type MaxBet = MaxBet;
+	type MaxTicketsPerAccount = MaxTicketsPerAccount;
+	type RevealPeriod = RevealPeriod;
+	type FinalisationThreshold = FinalisationThreshold;
+	type OnChainDrawDelay = OnChainDrawDelay;
+	type ClaimPeriod = ClaimPeriod;
+	type FinalisationDeadline = FinalisationDeadline;
+	type RefundBatchSize = RefundBatchSize;
+	type MaxTicketsPerSession = MaxTicketsPerSession;
+	type RetentionPeriod = RetentionPeriod;
+	type BetDeposit = BetDeposit;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: (1..=4).map(|account_id| (account_id, 1_000_000)).collect(),
+	}.assimilate_storage(&mut storage).unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| {
+		Balances::make_free_balance_be(&WeHub::account_id(), ExistentialDeposit::get());
+		WeHub::do_create_game(pallet_wehub::classic_game(TicketPrice::get())).unwrap();
+	});
+	ext
 }
@@ -1,15 +1,57 @@
 use crate::{mock::*};
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{assert_ok, assert_noop, traits::{Currency, OnFinalize, OnInitialize}};
+use sp_arithmetic::Percent;
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::{Hash, ValidateUnsigned},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+};
+use sp_std::convert::TryInto;
+
+const GAME_ID: crate::GameId = 0;
+
+fn numbers(numbers: Vec<u8>) -> crate::GuessNumbersOf<Test> {
+	numbers.try_into().unwrap()
+}
+
+fn commit(authority: u64, session_id: crate::SessionIdType, secret: [u8; 32]) -> frame_support::dispatch::DispatchResultWithPostInfo {
+	let payload = crate::CommitPayload {
+		public: UintAuthorityId(authority),
+		game_id: GAME_ID,
+		session_id,
+		commitment: <Test as frame_system::Config>::Hashing::hash_of(&secret),
+	};
+
+	WeHub::commit_session_secret(Origin::none(), payload, TestSignature(authority, vec![]))
+}
+
+fn reveal(authority: u64, session_id: crate::SessionIdType, secret: [u8; 32]) -> frame_support::dispatch::DispatchResultWithPostInfo {
+	let payload = crate::RevealPayload { public: UintAuthorityId(authority), game_id: GAME_ID, session_id, secret };
+
+	WeHub::reveal_session_secret(Origin::none(), payload, TestSignature(authority, vec![]))
+}
+
+fn submit(authority: u64, session_id: crate::SessionIdType, session_numbers: crate::GuessNumbersOf<Test>) -> frame_support::dispatch::DispatchResultWithPostInfo {
+	let payload = crate::SessionNumbersPayload {
+		public: UintAuthorityId(authority),
+		block_number: System::block_number(),
+		game_id: GAME_ID,
+		session_id,
+		session_numbers,
+	};
+
+	WeHub::finalize_the_session(Origin::none(), payload, TestSignature(authority, vec![]))
+}
 
 #[test]
 fn add_new_bet_works() {
 	new_test_ext().execute_with(|| {
-		let session_id = WeHub::session_id();
-		assert_eq!(WeHub::bets(session_id), vec![]);
+		let session_id = WeHub::session_id(GAME_ID);
+		assert_eq!(WeHub::bet_count(GAME_ID, session_id), 0);
 
-		let (account_id, guess_numbers, bet) = (1, [0; crate::GUESS_NUMBERS_COUNT], 100);
+		let (account_id, guess_numbers, bet) = (1, numbers(vec![1, 2, 3, 4, 5, 6]), 100);
 		
-		assert_ok!(WeHub::add_new_bet(Origin::signed(account_id), guess_numbers, bet));
+		assert_ok!(WeHub::add_new_bet(Origin::signed(account_id), GAME_ID, guess_numbers.clone(), bet));
 		
 		let bet = crate::Bet {
 			account_id,
@@ -17,49 +59,50 @@ fn add_new_bet_works() {
 			bet,
 		};
 
-		assert_eq!(WeHub::bets(session_id), vec![bet]);
+		assert_eq!(WeHub::bet_count(GAME_ID, session_id), 1);
+		assert_eq!(WeHub::bets((GAME_ID, session_id), 0), Some(bet));
 	});
 }
 
 #[test]
 fn next_session_id_works() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(WeHub::session_id(), 0);
+		assert_eq!(WeHub::session_id(GAME_ID), 0);
 		
-		assert_ok!(WeHub::next_session_id());
-		assert_eq!(WeHub::session_id(), 1);
+		assert_ok!(WeHub::next_session_id(GAME_ID));
+		assert_eq!(WeHub::session_id(GAME_ID), 1);
 		
-		JackBlock::set_session_id(crate::SessionIdType::MAX);
-		assert_eq!(WeHub::session_id(), crate::SessionIdType::MAX);
+		WeHub::set_session_id(GAME_ID, crate::SessionIdType::MAX);
+		assert_eq!(WeHub::session_id(GAME_ID), crate::SessionIdType::MAX);
 		
-		assert_noop!(WeHub::next_session_id(), crate::Error::<Test>::SessionIdOverflow);
+		assert_noop!(WeHub::next_session_id(GAME_ID), crate::Error::<Test>::SessionIdOverflow);
 	});
 }
 
 #[test]
 fn get_winners_works() {
 	new_test_ext().execute_with(|| {
-		let session_numbers = [5, 21, 48, 25, 34, 18];
+		let session_numbers = numbers(vec![5, 21, 48, 25, 34, 18]);
 
 		let session_bets = vec!(
 			crate::Bet {
 				account_id: 1,
-				guess_numbers: [33, 48, 18, 2, 5, 8],
+				guess_numbers: numbers(vec![33, 48, 18, 2, 5, 8]),
 				bet: 100,
 			},
 			crate::Bet {
 				account_id: 2,
-				guess_numbers: [9, 3, 1, 21, 43, 4],
+				guess_numbers: numbers(vec![9, 3, 1, 21, 43, 4]),
 				bet: 200,
 			},
 			crate::Bet {
 				account_id: 3,
-				guess_numbers: [8, 8, 8, 8, 8, 8],
+				guess_numbers: numbers(vec![8, 8, 8, 8, 8, 8]),
 				bet: 300,
 			},
 			crate::Bet {
 				account_id: 4,
-				guess_numbers: [42, 29, 8, 1, 5, 18],
+				guess_numbers: numbers(vec![42, 29, 8, 1, 5, 18]),
 				bet: 400,
 			},
 		);
@@ -70,6 +113,823 @@ fn get_winners_works() {
 			(session_bets[3].clone(), 2),
 		);
 
-		assert_eq!(WeHub::get_winners(session_numbers, session_bets), expected_result);
+		assert_eq!(WeHub::get_winners(&session_numbers, session_bets), expected_result);
+	});
+}
+
+#[test]
+fn add_new_bet_rejects_invalid_guess_numbers() {
+	new_test_ext().execute_with(|| {
+		let bet = TicketPrice::get();
+
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![0, 1, 2, 3, 4, 5]), bet),
+			crate::Error::<Test>::GuessNumberOutOfRange
+		);
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 11]), bet),
+			crate::Error::<Test>::GuessNumberOutOfRange
+		);
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![8, 8, 8, 8, 8, 8]), bet),
+			crate::Error::<Test>::DuplicateGuessNumber
+		);
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5]), bet),
+			crate::Error::<Test>::WrongNumberOfGuessNumbers
+		);
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), 1, numbers(vec![1, 2, 3, 4, 5, 6]), bet),
+			crate::Error::<Test>::GameDoesNotExist
+		);
+	});
+}
+
+#[test]
+fn get_winners_ignores_repeated_numbers() {
+	new_test_ext().execute_with(|| {
+		let session_numbers = numbers(vec![5, 5, 5, 1, 2, 3]);
+
+		let session_bets = vec!(
+			crate::Bet {
+				account_id: 1,
+				guess_numbers: numbers(vec![5, 5, 5, 5, 5, 5]),
+				bet: 100,
+			},
+		);
+
+		let expected_result = vec!(
+			(session_bets[0].clone(), 1),
+		);
+
+		assert_eq!(WeHub::get_winners(&session_numbers, session_bets), expected_result);
+	});
+}
+#[test]
+fn set_ticket_price_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(WeHub::games(GAME_ID).unwrap().ticket_price, TicketPrice::get());
+
+		assert_noop!(WeHub::set_ticket_price(Origin::signed(1), GAME_ID, 500), sp_runtime::DispatchError::BadOrigin);
+
+		assert_ok!(WeHub::set_ticket_price(Origin::root(), GAME_ID, 500));
+		assert_eq!(WeHub::games(GAME_ID).unwrap().ticket_price, 500);
+
+		assert_noop!(WeHub::set_ticket_price(Origin::root(), GAME_ID, 0), crate::Error::<Test>::ZeroTicketPrice);
+		let free_game = crate::Game { ticket_price: 0, ..WeHub::games(GAME_ID).unwrap() };
+		assert_noop!(WeHub::create_game(Origin::root(), free_game), crate::Error::<Test>::ZeroTicketPrice);
+	});
+}
+
+#[test]
+fn add_new_bet_checks_stake_bounds() {
+	new_test_ext().execute_with(|| {
+		let guess_numbers = numbers(vec![1, 2, 3, 4, 5, 6]);
+
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), GAME_ID, guess_numbers.clone(), TicketPrice::get() - 1),
+			crate::Error::<Test>::BetBelowTicketPrice
+		);
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), GAME_ID, guess_numbers, MaxBet::get() + 1),
+			crate::Error::<Test>::BetAboveMaximum
+		);
+	});
+}
+
+#[test]
+fn add_new_bets_enforces_tickets_per_account() {
+	new_test_ext().execute_with(|| {
+		let session_id = WeHub::session_id(GAME_ID);
+		let tickets = vec![numbers(vec![1, 2, 3, 4, 5, 6]), numbers(vec![2, 3, 4, 5, 6, 7])];
+
+		assert_noop!(WeHub::add_new_bets(Origin::signed(1), GAME_ID, vec![]), crate::Error::<Test>::NoTickets);
+
+		assert_ok!(WeHub::add_new_bets(Origin::signed(1), GAME_ID, tickets.clone()));
+		assert_eq!(WeHub::bet_count(GAME_ID, session_id), 2);
+		assert_eq!(WeHub::account_ticket_indices((GAME_ID, session_id), 1).to_vec(), vec![0, 1]);
+
+		assert_noop!(
+			WeHub::add_new_bets(Origin::signed(1), GAME_ID, tickets),
+			crate::Error::<Test>::TooManyTicketsPerAccount
+		);
+	});
+}
+
+#[test]
+fn session_numbers_from_reveals_works() {
+	new_test_ext().execute_with(|| {
+		let session_id = WeHub::session_id(GAME_ID);
+		assert_eq!(WeHub::session_numbers_from_reveals(GAME_ID, session_id), None);
+
+		crate::Reveals::<Test>::insert(GAME_ID, session_id, vec![(1, [1; 32]), (2, [2; 32])]);
+		let session_numbers = WeHub::session_numbers_from_reveals(GAME_ID, session_id).unwrap();
+
+		crate::Reveals::<Test>::insert(GAME_ID, session_id, vec![(2, [2; 32]), (1, [1; 32])]);
+		assert_eq!(WeHub::session_numbers_from_reveals(GAME_ID, session_id), Some(session_numbers.clone()));
+
+		assert_ok!(WeHub::ensure_valid_guess_numbers(&WeHub::games(GAME_ID).unwrap(), &session_numbers));
+	});
+}
+
+#[test]
+fn authorities_commit_and_reveal_secrets() {
+	new_test_ext().execute_with(|| {
+		crate::Authorities::<Test>::put(vec![1, 2, 3]);
+		for authority in 1..=3 {
+			assert_ok!(commit(authority, 0, [authority as u8; 32]));
+		}
+		assert_noop!(commit(1, 0, [1; 32]), crate::Error::<Test>::AlreadyCommitted);
+
+		System::set_block_number(5);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_noop!(commit(1, 0, [1; 32]), crate::Error::<Test>::CommitPhaseOver);
+
+		assert_noop!(reveal(1, 0, [9; 32]), crate::Error::<Test>::InvalidReveal);
+		assert_ok!(reveal(1, 0, [1; 32]));
+		assert_ok!(reveal(2, 0, [2; 32]));
+		assert_noop!(reveal(1, 0, [1; 32]), crate::Error::<Test>::AlreadyRevealed);
+
+		System::set_block_number(5 + RevealPeriod::get() + 1);
+		assert_noop!(reveal(3, 0, [3; 32]), crate::Error::<Test>::RevealPhaseOver);
+
+		let session_numbers = WeHub::session_numbers_from_reveals(GAME_ID, 0).unwrap();
+		assert_ok!(submit(1, 0, session_numbers.clone()));
+		assert_ok!(submit(2, 0, session_numbers));
+
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().status, crate::SessionStatus::Finalised);
+		assert_eq!(WeHub::missed_reveals(GAME_ID, 0), vec![3]);
+		assert!(WeHub::reveals(GAME_ID, 0).is_empty());
+		assert_eq!(WeHub::commitments((GAME_ID, 0), 1), None);
+
+		for _ in 1..RetentionPeriod::get() {
+			assert_ok!(WeHub::close_the_session(GAME_ID));
+		}
+		assert_eq!(WeHub::missed_reveals(GAME_ID, 0), vec![3]);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert!(WeHub::missed_reveals(GAME_ID, 0).is_empty());
+	});
+}
+
+/// Closes session 0 with every one of three authorities revealed, and returns the numbers drawn from the reveals.
+fn close_with_three_reveals() -> crate::GuessNumbersOf<Test> {
+	System::set_block_number(1);
+	crate::Authorities::<Test>::put(vec![1, 2, 3]);
+	for authority in 1..=3 {
+		assert_ok!(commit(authority, 0, [authority as u8; 32]));
+	}
+	assert_ok!(WeHub::close_the_session(GAME_ID));
+	for authority in 1..=3 {
+		assert_ok!(reveal(authority, 0, [authority as u8; 32]));
+	}
+
+	WeHub::session_numbers_from_reveals(GAME_ID, 0).unwrap()
+}
+
+#[test]
+fn sessions_are_finalised_at_quorum() {
+	new_test_ext().execute_with(|| {
+		let session_numbers = close_with_three_reveals();
+
+		assert_ok!(submit(1, 0, session_numbers.clone()));
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().status, crate::SessionStatus::Closed);
+		assert_noop!(submit(1, 0, session_numbers.clone()), crate::Error::<Test>::AlreadySubmittedSessionNumbers);
+
+		assert_ok!(submit(2, 0, session_numbers.clone()));
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().status, crate::SessionStatus::Finalised);
+		assert!(WeHub::pending_sessions(GAME_ID).is_empty());
+	});
+}
+
+#[test]
+fn conflicting_numbers_block_the_session_until_resolved() {
+	new_test_ext().execute_with(|| {
+		let session_numbers = close_with_three_reveals();
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+
+		let mut wrong_numbers = session_numbers.to_vec();
+		wrong_numbers[0] = (1..=10).find(|number| !session_numbers.contains(number)).unwrap();
+		let wrong_numbers = numbers(wrong_numbers);
+
+		assert_ok!(submit(1, 0, session_numbers.clone()));
+		assert_ok!(submit(2, 0, wrong_numbers.clone()));
+
+		assert!(System::events().iter().any(|record| {
+			record.event == Event::pallet_wehub(crate::Event::SessionNumbersConflict(GAME_ID, 0, 2, wrong_numbers.clone()))
+		}));
+		assert!(WeHub::conflicted_sessions(GAME_ID, 0));
+		assert_noop!(submit(3, 0, session_numbers.clone()), crate::Error::<Test>::SessionHasConflictingNumbers);
+		assert_eq!(WeHub::pending_sessions(GAME_ID), vec![0, 1]);
+		assert_noop!(submit(3, 1, session_numbers.clone()), crate::Error::<Test>::TryToFinalizeTheSessionWhichIsNotClosed);
+
+		assert_noop!(WeHub::resolve_session_conflict(Origin::signed(1), GAME_ID, 0), sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(WeHub::resolve_session_conflict(Origin::root(), GAME_ID, 0));
+		assert!(WeHub::session_numbers_submissions(GAME_ID, 0).is_empty());
+
+		assert_ok!(submit(2, 0, session_numbers.clone()));
+		assert_ok!(submit(3, 0, session_numbers));
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().status, crate::SessionStatus::Finalised);
+		assert_noop!(
+			WeHub::resolve_session_conflict(Origin::root(), GAME_ID, 0),
+			crate::Error::<Test>::SessionIsNotConflicted
+		);
+	});
+}
+
+#[test]
+fn finalisation_quorum_is_capped_by_authorities() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(WeHub::finalisation_quorum(), 1);
+
+		crate::Authorities::<Test>::put(vec![1, 2, 3]);
+		assert_eq!(WeHub::finalisation_quorum(), FinalisationThreshold::get());
+
+		crate::Authorities::<Test>::put(vec![1]);
+		assert_eq!(WeHub::finalisation_quorum(), 1);
+	});
+}
+
+#[test]
+fn on_chain_draw_finalizes_the_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeHub::set_draw_mode(Origin::root(), crate::DrawMode::OnChain));
+
+		System::set_block_number(5);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+
+		let draw_at = 5 + OnChainDrawDelay::get();
+		assert_eq!(WeHub::on_chain_draw_at(GAME_ID, 0), Some(draw_at));
+
+		WeHub::on_initialize(draw_at - 1);
+		assert_eq!(WeHub::closed_not_finalised_session(GAME_ID), Some(0));
+
+		WeHub::on_initialize(draw_at);
+		assert_eq!(WeHub::on_chain_draw_at(GAME_ID, 0), None);
+		assert_eq!(WeHub::closed_not_finalised_session(GAME_ID), None);
+	});
+}
+
+fn has_event(event: crate::Event<Test>) -> bool {
+	System::events().iter().any(|record| record.event == Event::pallet_wehub(event.clone()))
+}
+
+fn tier(min_hits: u8, percentage: u8) -> crate::PrizeTierOf<Test> {
+	crate::PrizeTier { min_hits, percentage: Percent::from_percent(percentage), fixed_prize: None }
+}
+
+#[test]
+fn unwon_and_unallocated_shares_roll_over_into_the_next_jackpot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WeHub::set_prize_table(Origin::root(), GAME_ID, vec![tier(3, 10), tier(5, 20), tier(6, 60)]));
+
+		assert_ok!(WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 1_000));
+		assert_ok!(WeHub::add_new_bet(Origin::signed(2), GAME_ID, numbers(vec![1, 2, 3, 7, 8, 9]), 1_000));
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		// Of the 1_800 left after the fee, the won 3 and 6 hit tiers pay 180 and 1_080.
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().rewards, 1_260);
+		assert!(!has_event(crate::Event::TierRolledOver(GAME_ID, 0, 3, 180)));
+		assert!(has_event(crate::Event::TierRolledOver(GAME_ID, 0, 5, 360)));
+		assert!(has_event(crate::Event::UnallocatedRolledOver(GAME_ID, 0, 180)));
+		// The unpaid fee rolls over as well.
+		assert!(has_event(crate::Event::RolledOver(GAME_ID, 0, 740)));
+		assert_eq!(WeHub::session_pot(GAME_ID, 0), 0);
+
+		assert_ok!(WeHub::add_new_bet(Origin::signed(3), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 1_000));
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 1, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		let jackpot = WeHub::sessions(GAME_ID, 1).unwrap().tiers.pop().unwrap();
+		assert_eq!(jackpot.share, 540 + 740);
+		assert_eq!(WeHub::rollover(GAME_ID), 90 + 180 + 90 + 100);
+	});
+}
+
+#[test]
+fn set_prize_table_validates_tiers() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(WeHub::games(GAME_ID).unwrap().prize_table, crate::default_prize_table());
+
+		let tier = |min_hits, percentage| crate::PrizeTier {
+			min_hits,
+			percentage: Percent::from_percent(percentage),
+			fixed_prize: None,
+		};
+
+		assert_noop!(
+			WeHub::set_prize_table(Origin::root(), GAME_ID, vec![tier(3, 50), tier(6, 51)]),
+			crate::Error::<Test>::PrizeTableExceedsPot
+		);
+		assert_noop!(
+			WeHub::set_prize_table(Origin::root(), GAME_ID, vec![tier(4, 10), tier(3, 10)]),
+			crate::Error::<Test>::InvalidPrizeTierHits
+		);
+		assert_noop!(
+			WeHub::set_prize_table(Origin::root(), GAME_ID, vec![tier(7, 10)]),
+			crate::Error::<Test>::InvalidPrizeTierHits
+		);
+
+		let prize_table = vec![tier(2, 10), tier(6, 90)];
+		assert_ok!(WeHub::set_prize_table(Origin::root(), GAME_ID, prize_table.clone()));
+		assert_eq!(WeHub::games(GAME_ID).unwrap().prize_table, prize_table);
+	});
+}
+
+#[test]
+fn fees_are_split_between_the_treasury_and_authorities() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		crate::Authorities::<Test>::put(vec![3, 4]);
+		assert_ok!(WeHub::set_operator_fee(Origin::root(), Percent::from_percent(10), Percent::from_percent(50), Some(5)));
+
+		assert_ok!(WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 7, 8, 9, 10]), 1_000));
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		let authority_balance = Balances::free_balance(3);
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().fee, 100);
+		assert_eq!(Balances::free_balance(5), 50);
+		assert_eq!(Balances::free_balance(3), authority_balance + 25);
+		assert!(has_event(crate::Event::FeeForTreasury(5, 50)));
+		assert!(has_event(crate::Event::RewardFeeForAuthority(4, 25)));
+		assert_eq!(WeHub::rollover(GAME_ID), 900);
+
+		// Without authorities the treasury takes their part.
+		crate::Authorities::<Test>::kill();
+		assert_eq!(WeHub::distribute_fees(100), 100);
+		assert_eq!(Balances::free_balance(5), 150);
+	});
+}
+
+#[test]
+fn fees_without_authorities_or_treasury_roll_over() {
+	new_test_ext().execute_with(|| {
+		assert!(WeHub::authorities().is_empty());
+		assert_eq!(WeHub::treasury_account(), None);
+
+		assert_ok!(WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 7, 8, 9, 10]), 1_000));
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		let pallet_balance = Balances::free_balance(WeHub::account_id());
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().fee, 100);
+		assert_eq!(Balances::free_balance(WeHub::account_id()), pallet_balance);
+		assert_eq!(WeHub::rollover(GAME_ID), 1_000);
+	});
+}
+
+/// Finalises session 0 with a jackpot ticket of account 1 and a losing one of account 2, each staking 1_000.
+/// The 1_800 left after the 10% fee pays the jackpot 1_350.
+fn finalise_with_a_jackpot() {
+	assert_ok!(WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 1_000));
+	assert_ok!(WeHub::add_new_bet(Origin::signed(2), GAME_ID, numbers(vec![1, 2, 7, 8, 9, 10]), 1_000));
+	assert_ok!(WeHub::close_the_session(GAME_ID));
+	assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+}
+
+#[test]
+fn winners_claim_their_rewards_once() {
+	new_test_ext().execute_with(|| {
+		finalise_with_a_jackpot();
+
+		let session_info = WeHub::sessions(GAME_ID, 0).unwrap();
+		assert_eq!((session_info.rewards, session_info.claimed), (1_350, 0));
+		assert_eq!(WeHub::rollover(GAME_ID), 650);
+
+		assert_noop!(WeHub::claim_reward(Origin::signed(2), GAME_ID, 0), crate::Error::<Test>::NoRewardToClaim);
+
+		let balance = Balances::free_balance(1);
+		assert_ok!(WeHub::claim_reward(Origin::signed(1), GAME_ID, 0));
+		assert_eq!(Balances::free_balance(1), balance + 1_350);
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().claimed, 1_350);
+		assert_eq!(WeHub::pending_winners_nft().len(), 1);
+
+		assert_noop!(WeHub::claim_reward(Origin::signed(1), GAME_ID, 0), crate::Error::<Test>::RewardAlreadyClaimed);
+	});
+}
+
+#[test]
+fn tier_winners_split_the_share_by_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 1_000));
+		assert_ok!(WeHub::add_new_bet(Origin::signed(2), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 3_000));
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		// 75% of the 3_600 left after the fee, split 1:3.
+		let jackpot = WeHub::sessions(GAME_ID, 0).unwrap().tiers.pop().unwrap();
+		assert_eq!((jackpot.winners, jackpot.winning_stake, jackpot.share), (2, 4_000, 2_700));
+
+		for (account_id, reward) in vec![(1, 675), (2, 2_025)] {
+			let balance = Balances::free_balance(account_id);
+			assert_ok!(WeHub::claim_reward(Origin::signed(account_id), GAME_ID, 0));
+			assert_eq!(Balances::free_balance(account_id), balance + reward);
+		}
+	});
+}
+
+#[test]
+fn claimable_reward_is_zero_once_claimed() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(WeHub::claimable_reward(GAME_ID, 0, &1), 0);
+		finalise_with_a_jackpot();
+
+		assert_eq!(WeHub::claimable_reward(GAME_ID, 0, &1), 1_350);
+		assert_eq!(WeHub::claimable_reward(GAME_ID, 0, &2), 0);
+
+		assert_ok!(WeHub::claim_reward(Origin::signed(1), GAME_ID, 0));
+		assert_eq!(WeHub::claimable_reward(GAME_ID, 0, &1), 0);
+	});
+}
+
+#[test]
+fn unclaimed_rewards_expire_into_the_rollover() {
+	new_test_ext().execute_with(|| {
+		finalise_with_a_jackpot();
+
+		for _ in 1..ClaimPeriod::get() {
+			assert_ok!(WeHub::close_the_session(GAME_ID));
+			assert_eq!(WeHub::rollover(GAME_ID), 650);
+		}
+
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_eq!(WeHub::rollover(GAME_ID), 2_000);
+		assert_noop!(WeHub::claim_reward(Origin::signed(1), GAME_ID, 0), crate::Error::<Test>::ClaimPeriodOver);
+	});
+}
+
+#[test]
+fn session_lifecycle_is_recorded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		crate::SessionPot::<Test>::insert(GAME_ID, 0, 1_000);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+
+		let closed = WeHub::sessions(GAME_ID, 0).unwrap();
+		assert_eq!(closed.status, crate::SessionStatus::Closed);
+		assert_eq!(closed.close_block, Some(5));
+		assert_eq!(closed.total_pot, 1_000);
+
+		let open = WeHub::sessions(GAME_ID, 1).unwrap();
+		assert_eq!(open.status, crate::SessionStatus::Open);
+		assert_eq!(open.start_block, 6);
+
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		let finalised = WeHub::sessions(GAME_ID, 0).unwrap();
+		assert_eq!(finalised.status, crate::SessionStatus::Finalised);
+		assert_eq!(finalised.session_numbers, Some(numbers(vec![1, 2, 3, 4, 5, 6])));
+		assert_eq!(finalised.fee, 100);
+		assert_eq!(
+			finalised.tiers.iter().map(|tier| (tier.min_hits, tier.winners)).collect::<Vec<_>>(),
+			vec![(3, 0), (4, 0), (5, 0), (6, 0)]
+		);
+		assert_eq!(finalised.rewards, 0);
+	});
+}
+
+#[test]
+fn pending_sessions_are_finalised_in_order() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+
+		assert_eq!(WeHub::pending_sessions(GAME_ID), vec![0, 1]);
+		assert_eq!(WeHub::closed_not_finalised_session(GAME_ID), Some(0));
+
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		assert_eq!(WeHub::pending_sessions(GAME_ID), vec![1]);
+		assert_eq!(WeHub::closed_not_finalised_session(GAME_ID), Some(1));
+	});
+}
+
+#[test]
+fn overdue_sessions_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+
+		assert!(!WeHub::expire_overdue_session(GAME_ID, 5 + FinalisationDeadline::get()));
+		assert_eq!(WeHub::pending_sessions(GAME_ID), vec![0]);
+
+		assert!(WeHub::expire_overdue_session(GAME_ID, 6 + FinalisationDeadline::get()));
+		assert_eq!(WeHub::pending_sessions(GAME_ID), Vec::<u128>::new());
+		assert_eq!(WeHub::expired_sessions(), vec![(GAME_ID, 0)]);
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().status, crate::SessionStatus::Expired);
+
+		assert_noop!(WeHub::refund_expired(Origin::signed(1), GAME_ID, 1, 10), crate::Error::<Test>::SessionIsNotExpired);
+	});
+}
+
+#[test]
+fn root_can_cancel_a_closed_session() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_noop!(WeHub::cancel_session(Origin::root(), GAME_ID, 0), crate::Error::<Test>::SessionIsNotClosed);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+
+		assert_noop!(WeHub::cancel_session(Origin::signed(1), GAME_ID, 0), sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(WeHub::cancel_session(Origin::root(), GAME_ID, 0));
+
+		assert_eq!(WeHub::pending_sessions(GAME_ID), Vec::<u128>::new());
+		assert_eq!(WeHub::expired_sessions(), vec![(GAME_ID, 0)]);
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().status, crate::SessionStatus::Cancelled);
+	});
+}
+
+#[test]
+fn expired_bets_are_refunded_in_batches() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for account_id in 1..=3 {
+			assert_ok!(WeHub::add_new_bet(Origin::signed(account_id), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 100));
+		}
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_ok!(WeHub::cancel_session(Origin::root(), GAME_ID, 0));
+
+		// Whatever is left in the pot once every bet is refunded rolls over.
+		crate::SessionPot::<Test>::mutate(GAME_ID, 0, |pot| *pot += 50);
+		Balances::make_free_balance_be(&WeHub::account_id(), Balances::free_balance(WeHub::account_id()) + 50);
+		let balances: Vec<u64> = (1..=3).map(Balances::free_balance).collect();
+
+		assert_ok!(WeHub::refund_expired(Origin::signed(4), GAME_ID, 0, 10));
+		assert_eq!(Balances::free_balance(1), balances[0] + 100);
+		assert_eq!(Balances::free_balance(2), balances[1] + 100);
+		assert_eq!(Balances::free_balance(3), balances[2]);
+		assert_eq!(WeHub::refund_cursor(GAME_ID, 0), 2);
+		assert_eq!(crate::SessionPot::<Test>::get(GAME_ID, 0), 150);
+		assert!(has_event(crate::Event::BetRefunded(GAME_ID, 0, 2, 100)));
+
+		assert_ok!(WeHub::refund_expired(Origin::signed(4), GAME_ID, 0, 10));
+		assert_eq!(Balances::free_balance(3), balances[2] + 100);
+		assert_eq!(WeHub::refund_cursor(GAME_ID, 0), 0);
+		assert_eq!(crate::SessionPot::<Test>::get(GAME_ID, 0), 0);
+		assert_eq!(WeHub::rollover(GAME_ID), 50);
+		assert!(WeHub::expired_sessions().is_empty());
+		assert!(has_event(crate::Event::SessionRefunded(GAME_ID, 0)));
+
+		assert_noop!(WeHub::refund_expired(Origin::signed(4), GAME_ID, 0, 10), crate::Error::<Test>::SessionIsNotExpired);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn settled_sessions_are_pruned_after_the_retention_period() {
+	new_test_ext().execute_with(|| {
+		crate::Bets::<Test>::insert((GAME_ID, 0), 0, crate::Bet {
+			account_id: 1,
+			guess_numbers: numbers(vec![1, 2, 3, 4, 5, 6]),
+			bet: 100,
+		});
+		crate::BetCount::<Test>::insert(GAME_ID, 0, 1);
+
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, 0, numbers(vec![1, 2, 3, 4, 5, 6])));
+		assert_noop!(WeHub::prune_session(Origin::signed(2), GAME_ID, 0), crate::Error::<Test>::SessionCannotBePruned);
+
+		for _ in 0..RetentionPeriod::get() {
+			assert_ok!(WeHub::close_the_session(GAME_ID));
+		}
+
+		// Closing sessions never prunes, so `on_finalize` stays cheap.
+		assert_eq!(WeHub::bet_count(GAME_ID, 0), 1);
+		assert_ok!(WeHub::prune_session(Origin::signed(2), GAME_ID, 0));
+
+		assert_eq!(WeHub::bet_count(GAME_ID, 0), 0);
+		assert_eq!(WeHub::bets((GAME_ID, 0), 0), None);
+	});
+}
+
+#[test]
+fn session_length_changes_from_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(WeHub::set_session_length(Origin::root(), GAME_ID, 0), crate::Error::<Test>::InvalidSessionLength);
+		assert_ok!(WeHub::set_session_length(Origin::root(), GAME_ID, 3));
+		assert_eq!(WeHub::session_close_block(GAME_ID), 5);
+
+		System::set_block_number(5);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+
+		assert_eq!(WeHub::games(GAME_ID).unwrap().session_length, 3);
+		assert_eq!(WeHub::session_close_block(GAME_ID), 8);
+	});
+}
+
+#[test]
+fn wall_clock_sessions_close_at_the_scheduled_time() {
+	new_test_ext().execute_with(|| {
+		let daily_at_20 = crate::SessionSchedule::WallClock { period: 86_400, offset: 72_000 };
+
+		MockTime::set_timestamp(86_400);
+		assert_ok!(WeHub::set_session_schedule(Origin::root(), GAME_ID, daily_at_20));
+		assert_eq!(WeHub::next_close_time(GAME_ID), Some(86_400 + 72_000));
+
+		let game = WeHub::games(GAME_ID).unwrap();
+
+		MockTime::set_timestamp(86_400 + 71_999);
+		assert!(!WeHub::session_due(GAME_ID, &game, 1));
+
+		MockTime::set_timestamp(86_400 + 72_000);
+		assert!(WeHub::session_due(GAME_ID, &game, 1));
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_eq!(WeHub::next_close_time(GAME_ID), Some(2 * 86_400 + 72_000));
+	});
+}
+
+#[test]
+fn paused_betting_rejects_bets() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(WeHub::pause(Origin::signed(1), crate::PauseTarget::Betting), sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(WeHub::pause(Origin::root(), crate::PauseTarget::Betting));
+		assert!(WeHub::is_paused(crate::PauseTarget::Betting));
+		assert!(!WeHub::is_paused(crate::PauseTarget::Drawing));
+
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 100),
+			crate::Error::<Test>::BettingPaused
+		);
+
+		assert_ok!(WeHub::unpause(Origin::root(), crate::PauseTarget::Betting));
+		assert!(!WeHub::is_paused(crate::PauseTarget::Betting));
+	});
+}
+
+#[test]
+fn paused_drawing_holds_sessions_back() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeHub::pause(Origin::root(), crate::PauseTarget::Drawing));
+
+		WeHub::on_finalize(5);
+		assert_eq!(WeHub::session_id(GAME_ID), 0);
+
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		let payload = crate::SessionNumbersPayload {
+			public: UintAuthorityId(1),
+			block_number: 5,
+			game_id: GAME_ID,
+			session_id: 0,
+			session_numbers: numbers(vec![1, 2, 3, 4, 5, 6]),
+		};
+		let call = crate::Call::finalize_the_session(payload, TestSignature(1, vec![]));
+		assert_eq!(
+			<WeHub as ValidateUnsigned>::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Custom(crate::PAUSED_TX_ERROR).into(),
+		);
+
+		let overdue = System::block_number() + FinalisationDeadline::get() + 1;
+		WeHub::on_initialize(overdue);
+		assert_eq!(WeHub::pending_sessions(GAME_ID), vec![0]);
+		assert!(WeHub::expired_sessions().is_empty());
+
+		assert_ok!(WeHub::unpause(Origin::root(), crate::PauseTarget::Drawing));
+		WeHub::on_initialize(overdue);
+		assert_eq!(WeHub::pending_sessions(GAME_ID), Vec::<u128>::new());
+		assert_eq!(WeHub::sessions(GAME_ID, 0).unwrap().status, crate::SessionStatus::Expired);
+	});
+}
+
+#[test]
+fn games_have_their_own_shape_and_sessions() {
+	new_test_ext().execute_with(|| {
+		let five_of_fifty = crate::Game {
+			pick_count: 5,
+			min_number: 1,
+			max_number: 50,
+			ticket_price: 200,
+			session_length: 10,
+			schedule: crate::SessionSchedule::Blocks,
+			prize_table: vec![],
+		};
+
+		assert_noop!(
+			WeHub::create_game(Origin::root(), crate::Game { max_number: 4, ..five_of_fifty.clone() }),
+			crate::Error::<Test>::InvalidGameNumbers
+		);
+		assert_ok!(WeHub::create_game(Origin::root(), five_of_fifty));
+		assert_eq!(WeHub::next_game_id(), 2);
+		assert_eq!(WeHub::session_close_block(1), 10);
+
+		assert_noop!(
+			WeHub::add_new_bet(Origin::signed(1), 1, numbers(vec![1, 2, 3, 4, 5]), 100),
+			crate::Error::<Test>::BetBelowTicketPrice
+		);
+
+		assert_ok!(WeHub::close_the_session(1));
+		assert_eq!(WeHub::session_id(1), 1);
+		assert_eq!(WeHub::session_id(GAME_ID), 0);
+
+		let game = WeHub::games(1).unwrap();
+		let session_numbers = WeHub::draw_session_numbers(&game, Default::default());
+		assert_ok!(WeHub::ensure_valid_guess_numbers(&game, &session_numbers));
+	});
+}
+
+#[test]
+fn tickets_are_bounded_by_the_max_pick_count() {
+	new_test_ext().execute_with(|| {
+		use codec::{Decode, Encode};
+
+		let too_many: Vec<u8> = (1..=MaxPickCount::get() as u8 + 1).collect();
+		assert!(TryInto::<crate::GuessNumbersOf<Test>>::try_into(too_many.clone()).is_err());
+		assert!(crate::GuessNumbersOf::<Test>::decode(&mut &too_many.encode()[..]).is_err());
+
+		let seven_of_ten = crate::Game { pick_count: 7, ..WeHub::games(GAME_ID).unwrap() };
+		assert_noop!(WeHub::create_game(Origin::root(), seven_of_ten), crate::Error::<Test>::InvalidGameNumbers);
+	});
+}
+
+#[test]
+fn v1_storage_is_migrated_to_game_zero() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{Blake2_128Concat, StorageHasher, storage::migration::put_storage_value};
+		use crate::migrations::v2::{OldBet, OldNFTRequestData};
+
+		let (open_session, closed_session, settled_session) = (5u128, 4u128, 3u128);
+		let old_bet = |account_id: u64| OldBet { account_id, guess_numbers: [1, 2, 3, 4, 5, 6] };
+		let bet_price: u64 = crate::migrations::v2::BET_PRICE.into();
+
+		put_storage_value(b"WeHub", b"SessionId", &[], open_session);
+		put_storage_value(b"WeHub", b"ClosedNotFinalisedSessionId", &[], closed_session);
+		// Account 1 holds one ticket more than `MaxTicketsPerAccount` in the open session.
+		let open_bets = vec![old_bet(1), old_bet(2), old_bet(1), old_bet(1), old_bet(1)];
+		put_storage_value(b"WeHub", b"Bets", &Blake2_128Concat::hash(&open_session.encode()), open_bets);
+		put_storage_value(b"WeHub", b"Bets", &Blake2_128Concat::hash(&closed_session.encode()), vec![old_bet(3)]);
+		put_storage_value(b"WeHub", b"Bets", &Blake2_128Concat::hash(&settled_session.encode()), vec![old_bet(4)]);
+		Balances::make_free_balance_be(&WeHub::account_id(), 1 + 6 * bet_price);
+		let balance = Balances::free_balance(1);
+		put_storage_value(b"WeHub", b"PendingWinnersNFT", &[], vec![OldNFTRequestData {
+			winner_account: 3u64,
+			reward: 100u64,
+			score: 6,
+			score_out_of: 6,
+			session_id: 3,
+		}]);
+		crate::StorageVersion::<Test>::put(crate::Releases::V1_0_0);
+
+		assert_ok!(crate::migrations::pre_migrate::<Test>());
+		crate::migrations::migrate::<Test>();
+		assert_ok!(crate::migrations::post_migrate::<Test>());
+
+		assert_eq!(WeHub::session_id(GAME_ID), open_session);
+		assert_eq!(WeHub::bet_count(GAME_ID, open_session), 4);
+		assert_eq!(WeHub::bet_count(GAME_ID, closed_session), 1);
+		assert_eq!(WeHub::bets((GAME_ID, open_session), 1), Some(crate::Bet {
+			account_id: 2,
+			guess_numbers: numbers(vec![1, 2, 3, 4, 5, 6]),
+			bet: bet_price,
+		}));
+		assert_eq!(WeHub::account_ticket_indices((GAME_ID, open_session), 2).to_vec(), vec![1]);
+		assert_eq!(WeHub::account_ticket_indices((GAME_ID, open_session), 1).to_vec(), vec![0, 2, 3]);
+		assert_eq!(Balances::free_balance(1), balance + bet_price);
+		assert_eq!(WeHub::session_pot(GAME_ID, open_session), 4 * bet_price);
+		assert_eq!(WeHub::rollover(GAME_ID), 0);
+
+		let settled_info = WeHub::sessions(GAME_ID, settled_session).unwrap();
+		assert_eq!(settled_info.status, crate::SessionStatus::Finalised);
+		assert_eq!(settled_info.total_pot, bet_price);
+		assert_eq!(WeHub::pending_sessions(GAME_ID), vec![closed_session]);
+		assert_eq!(WeHub::pending_winners_nft().iter().map(|request| request.game_id).collect::<Vec<_>>(), vec![GAME_ID]);
+		assert_eq!(WeHub::storage_version(), crate::Releases::V2_0_0);
+	});
+}
+
+#[test]
+fn runtime_api_helpers_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_eq!(WeHub::blocks_until_close(GAME_ID), Some(3));
+		assert_eq!(WeHub::blocks_until_close(1), None);
+
+		let session_id = WeHub::session_id(GAME_ID);
+		assert_ok!(WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 6]), 100));
+		assert_ok!(WeHub::add_new_bet(Origin::signed(2), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 7]), 100));
+		assert_ok!(WeHub::add_new_bet(Origin::signed(1), GAME_ID, numbers(vec![1, 2, 3, 4, 5, 8]), 100));
+		assert_ok!(WeHub::add_new_bet(Origin::signed(3), GAME_ID, numbers(vec![1, 2, 7, 8, 9, 10]), 100));
+
+		let tickets: Vec<_> = WeHub::account_tickets(GAME_ID, session_id, &1).into_iter()
+			.map(|bet| bet.guess_numbers)
+			.collect();
+		assert_eq!(tickets, vec![numbers(vec![1, 2, 3, 4, 5, 6]), numbers(vec![1, 2, 3, 4, 5, 8])]);
+
+		assert!(WeHub::session_winners(GAME_ID, session_id).is_empty());
+		System::set_block_number(5);
+		assert_ok!(WeHub::close_the_session(GAME_ID));
+		assert_ok!(WeHub::finalize_session_with_numbers(GAME_ID, session_id, numbers(vec![1, 2, 3, 4, 5, 6])));
+
+		let hits: Vec<_> = WeHub::session_winners(GAME_ID, session_id).into_iter()
+			.map(|(bet, hits)| (bet.account_id, hits))
+			.collect();
+		assert_eq!(hits, vec![(1, 6), (2, 5), (1, 5)]);
+
+		let outcomes = WeHub::session_outcomes(
+			System::events().into_iter().filter_map(|record| record.event.try_into().ok())
+		);
+		assert_eq!(outcomes.len(), 1);
+		assert_eq!((outcomes[0].game_id, outcomes[0].session_id), (GAME_ID, session_id));
+		assert_eq!(outcomes[0].winners.len(), 3);
+		assert_eq!(outcomes[0].payouts.len(), 3);
+	});
+}
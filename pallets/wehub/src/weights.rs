@@ -0,0 +1,272 @@
+//! Weights for pallet_wehub
+//!
+//! NOT YET MEASURED: the execution times below are estimates and the database terms follow the storage
+//! accessed by each call. Replace the whole file with the benchmark output on the reference hardware,
+//! with the benchmarks ranging over the runtime's `MaxTicketsPerSession`, `MaxTicketsPerAccount`,
+//! `RefundBatchSize` and `MaxPickCount`:
+//!
+//! cargo build --release --features runtime-benchmarks
+//! ./target/release/wehub-node benchmark --chain dev --execution wasm --wasm-execution compiled
+//!     --pallet pallet_wehub --extrinsic '*' --steps 50 --repeat 20 --output ./pallets/wehub/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_wehub.
+pub trait WeightInfo {
+	fn create_game(t: u32, ) -> Weight;
+	fn add_new_bet() -> Weight;
+	fn finalize_the_session(b: u32, w: u32, a: u32, ) -> Weight;
+	fn claim_reward(t: u32, ) -> Weight;
+	fn refund_expired(n: u32, ) -> Weight;
+	fn prune_session(b: u32, ) -> Weight;
+	fn add_nft_hash_to_winner(n: u32, ) -> Weight;
+	fn set_ticket_price() -> Weight;
+	fn commit_session_secret() -> Weight;
+	fn reveal_session_secret(a: u32, ) -> Weight;
+	fn set_prize_table(t: u32, ) -> Weight;
+	fn set_operator_fee() -> Weight;
+	fn set_draw_mode() -> Weight;
+	fn set_session_length() -> Weight;
+	fn set_session_schedule() -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn resolve_session_conflict() -> Weight;
+	fn cancel_session(a: u32, ) -> Weight;
+}
+
+/// Weights for pallet_wehub using the node template and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn create_game(t: u32, ) -> Weight {
+		(45_127_000 as Weight)
+			.saturating_add((618_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn add_new_bet() -> Weight {
+		(96_317_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn finalize_the_session(b: u32, w: u32, a: u32, ) -> Weight {
+		(142_806_000 as Weight)
+			.saturating_add((6_130_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((10_642_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add((58_904_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	fn claim_reward(t: u32, ) -> Weight {
+		(87_519_000 as Weight)
+			.saturating_add((4_317_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn refund_expired(n: u32, ) -> Weight {
+		(38_614_000 as Weight)
+			.saturating_add((52_371_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn prune_session(b: u32, ) -> Weight {
+		(41_208_000 as Weight)
+			.saturating_add((12_507_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(b as Weight)))
+	}
+	fn add_nft_hash_to_winner(n: u32, ) -> Weight {
+		(67_945_000 as Weight)
+			.saturating_add((312_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn set_ticket_price() -> Weight {
+		(27_846_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn commit_session_secret() -> Weight {
+		(39_052_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_session_secret(a: u32, ) -> Weight {
+		(52_390_000 as Weight)
+			.saturating_add((306_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_prize_table(t: u32, ) -> Weight {
+		(31_483_000 as Weight)
+			.saturating_add((541_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_operator_fee() -> Weight {
+		(22_115_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_draw_mode() -> Weight {
+		(19_204_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_session_length() -> Weight {
+		(27_322_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_session_schedule() -> Weight {
+		(33_617_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn pause() -> Weight {
+		(18_390_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unpause() -> Weight {
+		(18_761_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn resolve_session_conflict() -> Weight {
+		(29_075_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_session(a: u32, ) -> Weight {
+		(47_238_000 as Weight)
+			.saturating_add((2_104_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_game(t: u32, ) -> Weight {
+		(45_127_000 as Weight)
+			.saturating_add((618_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn add_new_bet() -> Weight {
+		(96_317_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn finalize_the_session(b: u32, w: u32, a: u32, ) -> Weight {
+		(142_806_000 as Weight)
+			.saturating_add((6_130_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((10_642_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add((58_904_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	fn claim_reward(t: u32, ) -> Weight {
+		(87_519_000 as Weight)
+			.saturating_add((4_317_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn refund_expired(n: u32, ) -> Weight {
+		(38_614_000 as Weight)
+			.saturating_add((52_371_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn prune_session(b: u32, ) -> Weight {
+		(41_208_000 as Weight)
+			.saturating_add((12_507_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(b as Weight)))
+	}
+	fn add_nft_hash_to_winner(n: u32, ) -> Weight {
+		(67_945_000 as Weight)
+			.saturating_add((312_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn set_ticket_price() -> Weight {
+		(27_846_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn commit_session_secret() -> Weight {
+		(39_052_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_session_secret(a: u32, ) -> Weight {
+		(52_390_000 as Weight)
+			.saturating_add((306_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_prize_table(t: u32, ) -> Weight {
+		(31_483_000 as Weight)
+			.saturating_add((541_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_operator_fee() -> Weight {
+		(22_115_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_draw_mode() -> Weight {
+		(19_204_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_session_length() -> Weight {
+		(27_322_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_session_schedule() -> Weight {
+		(33_617_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn pause() -> Weight {
+		(18_390_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unpause() -> Weight {
+		(18_761_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resolve_session_conflict() -> Weight {
+		(29_075_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_session(a: u32, ) -> Weight {
+		(47_238_000 as Weight)
+			.saturating_add((2_104_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+}
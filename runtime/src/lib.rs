@@ -6,7 +6,7 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
-use sp_std::prelude::*;
+use sp_std::{prelude::*, convert::TryInto};
 use sp_core::{
 	crypto::KeyTypeId,
 	OpaqueMetadata,
@@ -75,6 +75,9 @@ pub type Hash = sp_core::H256;
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
+/// The numbers of a lottery ticket or draw.
+pub type GuessNumbers = pallet_wehub::GuessNumbersOf<Runtime>;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -103,10 +106,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 
@@ -266,12 +269,49 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const MaxGames: u32 = 16;
+	pub const MaxPickCount: u32 = 10;
+	pub const MaxBet: Balance = 1_000_000_000_000;
+	pub const MaxTicketsPerAccount: u32 = 100;
+	pub const RevealPeriod: BlockNumber = 2;
+	pub const FinalisationThreshold: u32 = 2;
+	pub const OnChainDrawDelay: BlockNumber = 2;
+	pub const ClaimPeriod: u128 = 1_000;
+	pub const FinalisationDeadline: BlockNumber = HOURS;
+	pub const RefundBatchSize: u32 = 50;
+	// Kept conservative until the weights are benchmarked. A session loads every bet into memory when it
+	// is finalised, and the `SessionResults` event carries every ticket with a hit (about 60 bytes each),
+	// so 1_000 tickets make a 60 KiB event and Vec, far below the 5 MiB block length. Drawn from
+	// `on_initialize`, the finalisation is mandatory weight of about 1_000 bet reads, a small part of the
+	// 2 second block.
+	pub const MaxTicketsPerSession: u32 = 1_000;
+	pub const RetentionPeriod: u128 = 1_000;
+	pub const BetDeposit: Balance = 1_000_000;
+}
+
 /// Configure the wehub pallet in pallets/wehub.
 impl pallet_wehub::Config for Runtime {
 	type Event = Event;
 	type AuthorityId = pallet_wehub::crypto::TestAuthId;
 	type Call = Call;
 	type Currency = Balances;
+	type MaxGames = MaxGames;
+	type MaxPickCount = MaxPickCount;
+	type MaxBet = MaxBet;
+	type MaxTicketsPerAccount = MaxTicketsPerAccount;
+	type RevealPeriod = RevealPeriod;
+	type FinalisationThreshold = FinalisationThreshold;
+	type Randomness = RandomnessCollectiveFlip;
+	type UnixTime = Timestamp;
+	type OnChainDrawDelay = OnChainDrawDelay;
+	type ClaimPeriod = ClaimPeriod;
+	type FinalisationDeadline = FinalisationDeadline;
+	type RefundBatchSize = RefundBatchSize;
+	type MaxTicketsPerSession = MaxTicketsPerSession;
+	type RetentionPeriod = RetentionPeriod;
+	type BetDeposit = BetDeposit;
+	type WeightInfo = pallet_wehub::weights::SubstrateWeight<Runtime>;
 }
 
 impl orml_nft::Config for Runtime {
@@ -521,6 +561,53 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_wehub_rpc_runtime_api::WeHubApi<Block, AccountId, BlockNumber, Balance, GuessNumbers>
+		for Runtime {
+		fn session_id(game_id: pallet_wehub::GameId) -> pallet_wehub::SessionIdType {
+			WeHub::session_id(game_id)
+		}
+		fn blocks_until_close(game_id: pallet_wehub::GameId) -> Option<BlockNumber> {
+			WeHub::blocks_until_close(game_id)
+		}
+		fn pot() -> Balance {
+			WeHub::pot().1
+		}
+		fn tickets(
+			game_id: pallet_wehub::GameId,
+			session_id: pallet_wehub::SessionIdType,
+			account_id: AccountId,
+		) -> Vec<pallet_wehub::Bet<AccountId, Balance, GuessNumbers>> {
+			WeHub::account_tickets(game_id, session_id, &account_id)
+		}
+		fn pending_nft_requests() -> Vec<pallet_wehub::NFTRequestData<AccountId, Balance>> {
+			WeHub::pending_winners_nft()
+		}
+		fn session_results(
+			game_id: pallet_wehub::GameId,
+			session_id: pallet_wehub::SessionIdType,
+		) -> Option<pallet_wehub::SessionInfo<BlockNumber, Balance, GuessNumbers>> {
+			WeHub::sessions(game_id, session_id)
+		}
+		fn session_winners(
+			game_id: pallet_wehub::GameId,
+			session_id: pallet_wehub::SessionIdType,
+		) -> pallet_wehub::Winners<AccountId, Balance, GuessNumbers> {
+			WeHub::session_winners(game_id, session_id)
+		}
+		fn claimable_reward(
+			game_id: pallet_wehub::GameId,
+			session_id: pallet_wehub::SessionIdType,
+			account_id: AccountId,
+		) -> Balance {
+			WeHub::claimable_reward(game_id, session_id, &account_id)
+		}
+		fn finalised_sessions() -> Vec<pallet_wehub::SessionOutcome<AccountId, Balance, GuessNumbers>> {
+			WeHub::session_outcomes(
+				System::events().into_iter().filter_map(|record| record.event.try_into().ok())
+			)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -555,6 +642,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_wehub, WeHub);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)